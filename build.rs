@@ -0,0 +1,60 @@
+//! Generates the registry of solutions that the aggregated binary links, see `src/template/in_process.rs`.
+use std::fmt::Write;
+use std::{env, fs, path::Path};
+
+/// Solution binaries are named `<year>_<day>.rs`, e.g. `2016_01.rs`.
+fn is_solution(name: &str) -> bool {
+    let Some((year, day)) = name.split_once('_') else {
+        return false;
+    };
+    year.len() == 4 && day.len() == 2 && name.chars().all(|c| c.is_ascii_digit() || c == '_')
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // without the feature there is no registry, so edits to solutions don't need a rerun.
+    if env::var_os("CARGO_FEATURE_AGGREGATE").is_none() {
+        return;
+    }
+
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            (path.extension()? == "rs" && is_solution(&name)).then_some(name)
+        })
+        .collect();
+    names.sort();
+
+    let mut registry = String::new();
+
+    for name in &names {
+        let path = bin_dir.join(format!("{name}.rs"));
+        writeln!(
+            registry,
+            "#[path = {:?}]\nmod solution_{name};",
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+
+    registry
+        .push_str("\nconst SOLUTIONS: &[advent_of_code::template::in_process::Solution] = &[\n");
+    for name in &names {
+        writeln!(
+            registry,
+            "    advent_of_code::template::in_process::Solution {{ puzzle: solution_{name}::PUZZLE, solve: solution_{name}::solve_in_process }},"
+        )
+        .unwrap();
+    }
+    registry.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, registry).unwrap();
+}
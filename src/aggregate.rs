@@ -0,0 +1,14 @@
+//! All solutions of `src/bin/` linked into a single binary, so that `cargo all --in-process`
+//! and `cargo time --in-process` run every day without spawning `cargo run` for each of them.
+//! Only built with the `aggregate` feature.
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOC: advent_of_code::template::alloc::CountingAlloc =
+    advent_of_code::template::alloc::CountingAlloc;
+
+fn main() {
+    advent_of_code::template::in_process::main(SOLUTIONS);
+}
@@ -1,7 +1,7 @@
 // 2016
 // --- Day 1: No Time for a Taxicab ---
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2016, 1);
 
 use std::collections::HashSet;
 use std::hash::Hash;
@@ -197,13 +197,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4));
     }
 }
@@ -0,0 +1,74 @@
+advent_of_code::solution!(2016, 3);
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+
+fn parse_line(input: &str) -> Result<[u32; 3]> {
+    let nb = input
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<u32>, _>>()
+        .wrap_err_with(|| format!("invalid line {input:?}"))?;
+    nb.try_into()
+        .map_err(|_| eyre!("expected 3 sides, got line {input:?}"))
+}
+
+fn parse_input(input: &str) -> Result<Vec<[u32; 3]>> {
+    input
+        .strip_suffix("\n")
+        .unwrap_or(input)
+        .split("\n")
+        .map(parse_line)
+        .collect()
+}
+
+fn parse_input_p2(input: &str) -> Result<Vec<[u32; 3]>> {
+    let rows = parse_input(input)?;
+    if rows.len() % 3 != 0 {
+        bail!("line count should be a multiple of 3, got {}", rows.len());
+    }
+    Ok(rows
+        .chunks(3)
+        .flat_map(|c| {
+            // LAAAAAAZY... Could have transposed
+            let [[ta1, tb1, tc1], [ta2, tb2, tc2], [ta3, tb3, tc3]] = [c[0], c[1], c[2]];
+            [[ta1, ta2, ta3], [tb1, tb2, tb3], [tc1, tc2, tc3]]
+        })
+        .collect())
+}
+
+fn is_valid(triangle: &[u32; 3]) -> bool {
+    let &[a, b, c] = triangle;
+    (a + b) > c && (b + c) > a && (c + a) > b
+}
+
+pub fn part_one(input: &str) -> Result<u32> {
+    Ok(parse_input(input)?.into_iter().filter(is_valid).count() as u32)
+}
+
+pub fn part_two(input: &str) -> Result<u32> {
+    Ok(parse_input_p2(input)?.into_iter().filter(is_valid).count() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(part_one("1 2").is_err());
+        assert!(part_one("1 2 x").is_err());
+        assert!(part_two("1 2 3\n4 5 6").is_err());
+    }
+}
@@ -0,0 +1,130 @@
+advent_of_code::solution!(2016, 4, parse = parse_input);
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+pub struct Entry {
+    room: u32,
+    checksum: Vec<char>,
+    name: Vec<char>,
+}
+
+fn rotate_char(c: char, shift: u8) -> char {
+    let a: u8 = b'a';
+
+    match c {
+        '-' => ' ',
+        'a'..='z' => (((c as u8) - a + shift) % 26 + a) as char,
+        _ => c,
+    }
+}
+
+impl Entry {
+    fn parse(re: &Regex, s: &str) -> Result<Entry> {
+        let capture = re.captures(s).ok_or_else(|| eyre!("invalid room {s:?}"))?;
+        let (_, [name, _, room, checksum]) = capture.extract();
+        if checksum.len() != 5 {
+            bail!("invalid checksum length in room {s:?}");
+        }
+        Ok(Entry {
+            room: room
+                .parse::<u32>()
+                .wrap_err_with(|| format!("invalid sector id in room {s:?}"))?,
+            checksum: checksum.chars().collect(),
+            name: name.chars().collect(),
+        })
+    }
+
+    fn count_letters(&self) -> Vec<(char, usize)> {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in &self.name {
+            *counts.entry(*c).or_default() += 1;
+        }
+        counts.into_iter().filter(|(c, _)| *c != '-').collect()
+    }
+
+    fn is_valid(&self) -> bool {
+        let mut letters_count = self.count_letters();
+        letters_count.sort_by(|(chr1, cnt1), (chr2, cnt2)| match cnt2.cmp(cnt1) {
+            Ordering::Equal => chr1.cmp(chr2),
+            cmp => cmp,
+        });
+        let letters_count: Vec<char> = letters_count
+            .into_iter()
+            .take(5)
+            .map(|(chr, _)| chr)
+            .collect();
+
+        letters_count == self.checksum
+    }
+
+    fn rotated_name(&self) -> String {
+        let shift: u8 = (self.room % 26) as u8;
+        (*self.name)
+            .iter()
+            .map(|c| rotate_char(*c, shift))
+            .collect()
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Entry>> {
+    let re = Regex::new(r"^(([a-z]+-)+)(\d+)\[([a-z]{5})\]$").unwrap();
+    input
+        .strip_suffix("\n")
+        .unwrap_or(input)
+        .split("\n")
+        .map(|s| Entry::parse(&re, s))
+        .collect()
+}
+
+// The parse error is shared by both parts, so each part reports a copy of it.
+fn valid_entries(entries: &Result<Vec<Entry>>) -> Result<impl Iterator<Item = &Entry>> {
+    let entries = entries.as_ref().map_err(|e| eyre!("{e:#}"))?;
+    Ok(entries.iter().filter(|e| e.is_valid()))
+}
+
+pub fn part_one(entries: &Result<Vec<Entry>>) -> Result<u32> {
+    Ok(valid_entries(entries)?.map(|e| e.room).sum())
+}
+
+pub fn part_two(entries: &Result<Vec<Entry>>) -> Result<u32> {
+    let res: Vec<_> = valid_entries(entries)?
+        .map(|e| (e.rotated_name(), e.room))
+        .collect();
+    for (name, room) in res {
+        if name.as_str().starts_with("north") {
+            return Ok(room);
+        }
+    }
+    bail!("no room name starts with \"north\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert_eq!(result.unwrap(), 1857);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(part_one(&parse_input("aaaaa-bbb-z-y-x-123[abxyz]\nnot a room")).is_err());
+        assert!(part_one(&parse_input("a-b-c-99999999999[abcde]")).is_err());
+        assert!(part_two(&parse_input("a-b-c-1[abc]")).is_err());
+    }
+}
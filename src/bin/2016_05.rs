@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(2016, 5);
 
 fn get_valid_digests(prefix: &str) -> impl Iterator<Item = String> + '_ {
     (1..).flat_map(move |x| {
@@ -57,13 +57,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some("18f47a30".into()));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some("05ace8e3".into()));
     }
 }
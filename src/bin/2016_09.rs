@@ -0,0 +1,82 @@
+advent_of_code::solution!(2016, 9);
+
+use color_eyre::eyre::{eyre, Result};
+
+fn count_uncompressed_length(input: &str, with_recursion: bool) -> Result<usize> {
+    let mut chars = input.chars().peekable();
+    let mut count: usize = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => {
+                // Fetch marker
+                let inner = chars.clone().take_while(|c| *c != ')').collect::<String>();
+
+                // Skip marker
+                for _ in 0..(inner.len() + 1) {
+                    chars.next();
+                }
+
+                // Parse marker
+                let marker = inner;
+                let inner: Vec<usize> = marker
+                    .split("x")
+                    .map(|s| s.parse::<usize>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| eyre!("invalid marker ({marker}): {e}"))?;
+                if inner.len() < 2 {
+                    return Err(eyre!("invalid marker ({marker})"));
+                }
+
+                // Fetch repeated string
+                let repeated = chars.clone().take(inner[0]).collect::<String>();
+                let repeated_length: usize = if with_recursion {
+                    count_uncompressed_length(&repeated.to_string(), with_recursion)?
+                } else {
+                    repeated.len()
+                };
+
+                // Add repeated to the count and skip it
+                count += repeated_length * inner[1];
+                for _ in 0..(inner[0]) {
+                    chars.next();
+                }
+            }
+            ')' => {
+                println!("Found closing parent (?!)");
+            }
+            ' ' => (),
+            _ => count += 1,
+        }
+    }
+    Ok(count)
+}
+
+pub fn part_one(input: &str) -> Result<usize> {
+    let input = input.strip_suffix("\n").unwrap_or(input);
+    count_uncompressed_length(input, false)
+}
+
+pub fn part_two(input: &str) -> Result<usize> {
+    let input = input.strip_suffix("\n").unwrap_or(input);
+    count_uncompressed_length(input, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!(
+        PUZZLE,
+        [
+            advent,
+            single_marker,
+            two_markers,
+            nested_marker,
+            overlapping_markers,
+            long_chain
+        ],
+        part_one = 1,
+        part_two = 2,
+    );
+}
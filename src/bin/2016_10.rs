@@ -1,4 +1,4 @@
-advent_of_code::solution!(10);
+advent_of_code::solution!(2016, 10);
 
 use color_eyre::eyre::{eyre, Report, Result};
 use std::collections::HashMap;
@@ -184,13 +184,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(30));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(2016, 12);
 
 // Could do with just a `char` but less place for confusion that way
 #[derive(Debug, Clone, Copy)]
@@ -186,13 +186,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(42));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(42));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(14);
+advent_of_code::solution!(2016, 14);
 
 #[derive(Debug, Clone)]
 struct Key {
@@ -193,13 +193,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(22728));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(22551));
     }
 }
@@ -1,94 +1,297 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::cli;
+use advent_of_code::template::commands::{
+    all, download, read, scaffold, solve, test_day, time, verify, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, Shell};
+    use advent_of_code::template::commands::{scaffold, time};
+    use advent_of_code::template::input::InputSource;
+    use advent_of_code::template::selection::{DayList, DaySelection};
+    use advent_of_code::template::{runner::BenchConfig, PuzzleId, Year};
+    use std::{env, ffi::OsString, process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: String,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: InputSource,
+        },
+        WatchDay {
+            puzzle: PuzzleId,
+            release: bool,
+            input: InputSource,
         },
         All {
+            year: Year,
+            selection: DaySelection,
             release: bool,
+            in_process: bool,
+            jobs: Option<usize>,
+            timeout: Option<Duration>,
         },
         Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+            year: Year,
+            selection: DaySelection,
+            options: time::TimeOptions,
+        },
+        Verify {
+            year: Year,
+            selection: DaySelection,
+        },
+        TestDay {
+            year: Year,
+            selection: DaySelection,
+            release: bool,
+        },
+        Completions {
+            shell: Shell,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Reads the `--year` option, falling back to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env()
+                .ok_or_else(|| "no year specified, pass `--year` or set `AOC_YEAR`.".into()),
+        }
+    }
+
+    /// Reads the `--year` option and the day number of a single-day command.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        let day = args.free_from_str().map_err(|e| match e {
+            pico_args::Error::MissingArgument => "missing day number.".into(),
+            e => Box::<dyn std::error::Error>::from(e),
+        })?;
+        Ok(PuzzleId::new(year, day))
+    }
+
+    /// Reads `--store` and the optional parts of the readme table, falling back to the
+    /// `AOC_README_EXTRAS` environment variable.
+    fn parse_store(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<time::TableOptions>, Box<dyn std::error::Error>> {
+        let store = args.contains("--store");
+        let extras: Option<time::TableOptions> = args.opt_value_from_str("--readme-extras")?;
+
+        if !store {
+            return match extras {
+                Some(_) => Err("`--readme-extras` is only used with `--store`.".into()),
+                None => Ok(None),
+            };
+        }
+
+        match extras {
+            Some(extras) => Ok(Some(extras)),
+            None => match env::var("AOC_README_EXTRAS") {
+                Ok(extras) => Ok(Some(extras.parse()?)),
+                Err(_) => Ok(Some(time::TableOptions::default())),
+            },
+        }
+    }
+
+    /// Reads the `--timeout` option in seconds. A solution running in-process can't be stopped.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+        in_process: bool,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        let Some(seconds) = args.opt_value_from_str::<_, f64>("--timeout")? else {
+            return Ok(None);
+        };
+
+        if in_process {
+            return Err("`--timeout` is not supported with `--in-process`.".into());
+        }
+
+        Duration::try_from_secs_f64(seconds)
+            .ok()
+            .filter(|timeout| !timeout.is_zero())
+            .map(Some)
+            .ok_or_else(|| "`--timeout` expects a positive number of seconds.".into())
+    }
+
+    /// Reads the day selection of a multi-day command, e.g. `1-7,12 --unsolved`.
+    /// Options with values have to be read before, as the list is a free argument.
+    fn parse_selection(
+        args: &mut pico_args::Arguments,
+    ) -> Result<DaySelection, Box<dyn std::error::Error>> {
+        Ok(DaySelection {
+            unsolved: args.contains("--unsolved"),
+            slow: args.contains("--slow"),
+            days: args.opt_free_from_str::<DayList>()?,
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut raw: Vec<String> = env::args().skip(1).collect();
+
+        let Some(command) = raw.first().and_then(|name| cli::find_command(name)) else {
+            match raw.first() {
+                None => eprintln!("No command specified."),
+                Some(x) if cli::wants_help(&raw) && x.starts_with('-') => {
+                    print!("{}", cli::usage());
+                    process::exit(0);
+                }
+                Some(x) => eprintln!("Unknown command: {x}"),
+            }
+            eprintln!();
+            eprint!("{}", cli::usage());
+            process::exit(1);
+        };
+
+        if cli::wants_help(&raw[1..]) {
+            print!("{}", command.help());
+            process::exit(0);
+        }
+
+        command.check_flags(&raw[1..])?;
+
+        // pico-args does not support options with an optional value like `--example [name]`.
+        let input = match raw.first().map(String::as_str) {
+            Some("solve" | "watch-day") => InputSource::take_from_args(&mut raw)?,
+            _ => InputSource::default(),
+        };
+        let mut args =
+            pico_args::Arguments::from_vec(raw.into_iter().map(OsString::from).collect());
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let year = parse_year(&mut args)?;
+                let release = args.contains("--release");
+                let in_process = args.contains("--in-process");
+                let jobs = args.opt_value_from_str("--jobs")?;
+                let timeout = parse_timeout(&mut args, in_process)?;
+
+                AppArguments::All {
+                    year,
+                    selection: parse_selection(&mut args)?,
+                    release,
+                    in_process,
+                    jobs,
+                    timeout,
+                }
+            }
             Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+                let year = parse_year(&mut args)?;
+                let in_process = args.contains("--in-process");
+                // regression threshold in percent, only used with `--compare`.
+                let threshold: f64 = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+                let options = time::TimeOptions {
+                    run_all: args.contains("--all"),
+                    store: parse_store(&mut args)?,
+                    bench: BenchConfig::from_args(&mut args)?,
+                    in_process,
+                    compare: args.contains("--compare").then_some(threshold / 100.0),
+                    memory: args.contains("--memory"),
+                    timeout: parse_timeout(&mut args, in_process)?,
+                };
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+                    year,
+                    selection: parse_selection(&mut args)?,
+                    options,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
+                selection: parse_selection(&mut args)?,
+            },
+            Some("test-day") => {
+                let year = parse_year(&mut args)?;
+                let all = args.contains("--all");
+                let release = args.contains("--release");
+                let selection = parse_selection(&mut args)?;
+
+                if all != selection.is_empty() {
+                    return Err("pass either days to test or `--all`.".into());
+                }
+
+                AppArguments::TestDay {
+                    year,
+                    selection,
+                    release,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+                puzzle: parse_puzzle(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("scaffold") => {
+                // options with values have to be read before the free day argument.
+                let template = args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.to_string());
+
+                AppArguments::Scaffold {
+                    puzzle: parse_puzzle(&mut args)?,
+                    download: args.contains("--download"),
+                    overwrite: args.contains("--overwrite"),
+                    template,
+                }
+            }
+            Some("solve") => {
+                let submit = args.opt_value_from_str("--submit")?;
+                if submit.is_some_and(|part| part != 1 && part != 2) {
+                    return Err("`--submit` expects part 1 or 2.".into());
+                }
+
+                AppArguments::Solve {
+                    puzzle: parse_puzzle(&mut args)?,
+                    release: args.contains("--release"),
+                    submit,
+                    dhat: args.contains("--dhat"),
+                    input,
+                }
+            }
+            Some("watch-day") => AppArguments::WatchDay {
+                puzzle: parse_puzzle(&mut args)?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                input,
+            },
+            Some("completions") => AppArguments::Completions {
+                shell: args.free_from_str()?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            // every command of the spec is handled above.
+            _ => unreachable!("unhandled command `{}`", command.name),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            return Err(format!(
+                "unexpected argument(s): {remaining:?}. Run `cargo {} --help` for usage.",
+                command.name
+            )
+            .into());
         }
 
         Ok(app_args)
@@ -102,33 +305,59 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                selection,
+                release,
+                in_process,
+                jobs,
+                timeout,
+            } => all::handle(year, &selection, release, in_process, jobs, timeout),
+            AppArguments::Time {
+                year,
+                selection,
+                options,
+            } => time::handle(year, &selection, options),
+            AppArguments::Verify { year, selection } => verify::handle(year, &selection),
+            AppArguments::TestDay {
+                year,
+                selection,
+                release,
+            } => test_day::handle(year, &selection, release),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
             AppArguments::Scaffold {
-                day,
+                puzzle,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // download first, so that the scaffold can use the puzzle description.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
+                scaffold::handle(puzzle, overwrite, &template);
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+            } => solve::handle(puzzle, release, dhat, submit, &input),
+            AppArguments::WatchDay {
+                puzzle,
+                release,
+                input,
+            } => watch::handle(puzzle, release, &input),
+            AppArguments::Completions { shell } => print!("{}", cli::completions(shell)),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, false, scaffold::DEFAULT_TEMPLATE);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,130 @@
+/// A global allocator that counts allocations, enabled with the `count-allocs` feature.
+/// Unlike dhat, it only keeps a few atomic counters, so it is cheap enough to leave on while benching.
+/// The `solution!` macro installs it in each solution binary.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+use crate::template::timings::Memory;
+
+/// Counters shared by all threads. Concurrent runs therefore count each other's allocations.
+struct Counters {
+    allocations: AtomicU64,
+    total_bytes: AtomicU64,
+    current_bytes: AtomicU64,
+    peak_bytes: AtomicU64,
+}
+
+impl Counters {
+    const fn new() -> Self {
+        Self {
+            allocations: AtomicU64::new(0),
+            total_bytes: AtomicU64::new(0),
+            current_bytes: AtomicU64::new(0),
+            peak_bytes: AtomicU64::new(0),
+        }
+    }
+
+    fn allocated(&self, size: usize) {
+        let size = size as u64;
+        self.allocations.fetch_add(1, Relaxed);
+        self.total_bytes.fetch_add(size, Relaxed);
+        let current = self.current_bytes.fetch_add(size, Relaxed) + size;
+        self.peak_bytes.fetch_max(current, Relaxed);
+    }
+
+    fn deallocated(&self, size: usize) {
+        self.current_bytes.fetch_sub(size as u64, Relaxed);
+    }
+
+    /// The peak is relative to the heap in use before `func` is called, e.g. not counting the input.
+    fn measure<T>(&self, func: impl FnOnce() -> T) -> (T, Memory) {
+        let allocations = self.allocations.load(Relaxed);
+        let total_bytes = self.total_bytes.load(Relaxed);
+        let baseline = self.current_bytes.load(Relaxed);
+        self.peak_bytes.store(baseline, Relaxed);
+
+        let result = func();
+
+        let memory = Memory {
+            allocations: self.allocations.load(Relaxed) - allocations,
+            total_bytes: self.total_bytes.load(Relaxed) - total_bytes,
+            peak_bytes: self.peak_bytes.load(Relaxed).saturating_sub(baseline),
+        };
+
+        (result, memory)
+    }
+}
+
+static COUNTERS: Counters = Counters::new();
+
+/// Forwards to the system allocator while counting. A `realloc` counts as a new allocation.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            COUNTERS.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            COUNTERS.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        COUNTERS.deallocated(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            COUNTERS.deallocated(layout.size());
+            COUNTERS.allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Count the allocations of `func`. Only meaningful if [`CountingAlloc`] is the global allocator.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Memory) {
+    COUNTERS.measure(func)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Counters;
+    use crate::template::timings::Memory;
+
+    #[test]
+    fn counts_allocations() {
+        let counters = Counters::new();
+        // allocated before the measurement, e.g. the input.
+        counters.allocated(1000);
+
+        let ((), memory) = counters.measure(|| {
+            counters.allocated(100);
+            counters.allocated(50);
+            counters.deallocated(100);
+            counters.allocated(20);
+            counters.deallocated(1000);
+        });
+
+        assert_eq!(
+            memory,
+            Memory {
+                allocations: 3,
+                total_bytes: 170,
+                peak_bytes: 150,
+            }
+        );
+    }
+}
@@ -0,0 +1,413 @@
+/// Ledger of submitted answers, used to avoid resubmitting answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// The verdict returned by the server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+}
+
+impl Verdict {
+    /// Parse the verdict from the message sent back by the server.
+    /// Returns `None` for messages that do not judge the answer, e.g. when the part was already solved.
+    pub fn from_message(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if message.contains("answer too recently") {
+            Some(Verdict::RateLimited)
+        } else if message.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if message.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if message.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else {
+            None
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            _ => Err(format!("Unknown verdict `{s}`.")),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadySolved(String),
+    KnownWrong(Verdict),
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Rejection::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Rejection::NotBelow(bound) => {
+                write!(f, "this answer is not below `{bound}`, which was too high.")
+            }
+            Rejection::NotAbove(bound) => {
+                write!(f, "this answer is not above `{bound}`, which was too low.")
+            }
+        }
+    }
+}
+
+/// Represents a single submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Represents every answer submitted so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Submission>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    /// Fails if the file cannot be read or parsed.
+    pub fn load() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(s) => Answers::try_from(s).map_err(|e| format!("{ANSWERS_FILE_PATH}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("{ANSWERS_FILE_PATH}: {e}")),
+        }
+    }
+
+    /// Rehydrate answers from a JSON file, reporting errors and falling back to an empty ledger.
+    /// Only use this where the ledger is not written back.
+    pub fn read_from_file() -> Self {
+        Answers::load().unwrap_or_else(|e| {
+            eprintln!("Failed to read answer ledger {e}");
+            Answers::default()
+        })
+    }
+
+    /// Record the verdict for a submitted answer.
+    pub fn record(&mut self, puzzle: PuzzleId, part: u8, answer: &str, verdict: Verdict) {
+        self.data.push(Submission {
+            puzzle,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    fn submissions(&self, puzzle: PuzzleId, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.puzzle == puzzle && s.part == part)
+    }
+
+    /// Returns the accepted answer of a part, if any.
+    pub fn correct_answer(&self, puzzle: PuzzleId, part: u8) -> Option<&str> {
+        self.submissions(puzzle, part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Check whether submitting `answer` could be useful given previous verdicts.
+    pub fn check(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Rejection> {
+        if let Some(correct) = self.correct_answer(puzzle, part) {
+            return Err(Rejection::AlreadySolved(correct.to_string()));
+        }
+
+        if let Some(previous) = self
+            .submissions(puzzle, part)
+            .find(|s| s.answer == answer && s.verdict != Verdict::RateLimited)
+        {
+            return Err(Rejection::KnownWrong(previous.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            self.submissions(puzzle, part)
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(upper) = bound(Verdict::TooHigh).min() {
+            if value >= upper {
+                return Err(Rejection::NotBelow(upper.to_string()));
+            }
+        }
+
+        if let Some(lower) = bound(Verdict::TooLow).max() {
+            if value <= lower {
+                return Err(Rejection::NotAbove(lower.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year.to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected submission.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            puzzle: PuzzleId::new(year, day),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Rejection, Verdict};
+    use crate::puzzle;
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(puzzle!(2016, 1), 1, "500", Verdict::TooHigh);
+        answers.record(puzzle!(2016, 1), 1, "100", Verdict::TooLow);
+        answers.record(puzzle!(2016, 1), 1, "300", Verdict::Wrong);
+        answers.record(puzzle!(2016, 1), 1, "250", Verdict::RateLimited);
+        answers.record(puzzle!(2016, 2), 1, "ABCD", Verdict::Correct);
+        answers
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_message("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer; your answer is too high."),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer. If you're stuck, ..."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::from_message("You gave an answer too recently; you have 30s left to wait."),
+            Some(Verdict::RateLimited)
+        );
+        assert_eq!(
+            Verdict::from_message("You don't seem to be solving the right level."),
+            None
+        );
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(puzzle!(2016, 1), 1, "300"),
+            Err(Rejection::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(
+            answers.check(puzzle!(2016, 2), 1, "ABCE"),
+            Err(Rejection::AlreadySolved("ABCD".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_out_of_bounds_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(puzzle!(2016, 1), 1, "600"),
+            Err(Rejection::NotBelow("500".into()))
+        );
+        assert_eq!(
+            answers.check(puzzle!(2016, 1), 1, "100"),
+            Err(Rejection::KnownWrong(Verdict::TooLow))
+        );
+        assert_eq!(
+            answers.check(puzzle!(2016, 1), 1, "50"),
+            Err(Rejection::NotAbove("100".into()))
+        );
+    }
+
+    #[test]
+    fn allows_new_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.check(puzzle!(2016, 1), 1, "250"), Ok(()));
+        assert_eq!(answers.check(puzzle!(2016, 1), 1, "abc"), Ok(()));
+        assert_eq!(answers.check(puzzle!(2016, 1), 2, "600"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.data.len(), answers.data.len());
+        assert_eq!(parsed.data[0].verdict, Verdict::TooHigh);
+        assert_eq!(parsed.correct_answer(puzzle!(2016, 2), 1), Some("ABCD"));
+    }
+}
@@ -0,0 +1,426 @@
+/// Built-in client for the Advent of Code website.
+/// Falls back to the "aoc-cli" command-line when no session cookie is configured.
+use std::{env, fmt::Display, fs, io, path::PathBuf};
+
+use crate::template::{aoc_cli, PuzzleId};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/mx42/aoc2016 (advent_of_code template)";
+static SESSION_ENV: &str = "AOC_SESSION";
+static SESSION_FILE: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    Http(String),
+    Status(u16),
+    IO(io::Error),
+    Cli(aoc_cli::AocCommandError),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `{SESSION_ENV}` or write it to `~/{SESSION_FILE}`."
+            ),
+            AocClientError::Http(e) => write!(f, "request failed: {e}"),
+            AocClientError::Status(code) => {
+                write!(f, "server responded with status {code}.")
+            }
+            AocClientError::IO(e) => write!(f, "{e}"),
+            AocClientError::Cli(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<aoc_cli::AocCommandError> for AocClientError {
+    fn from(e: aoc_cli::AocCommandError) -> Self {
+        AocClientError::Cli(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, _) => AocClientError::Status(code),
+            ureq::Error::Transport(t) => AocClientError::Http(t.to_string()),
+        }
+    }
+}
+
+/// Network operations needed by the template commands.
+pub trait Backend {
+    /// Download the puzzle input and description to the `data` folder.
+    fn download(&self, puzzle: PuzzleId) -> Result<(), AocClientError>;
+
+    /// Print the puzzle description to the terminal.
+    fn read(&self, puzzle: PuzzleId) -> Result<(), AocClientError>;
+
+    /// Submit an answer, returning the message sent back by the server.
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, AocClientError>;
+}
+
+/// Returns the native client if a session cookie is configured, the "aoc-cli" backend otherwise.
+pub fn backend() -> Result<Box<dyn Backend>, AocClientError> {
+    match HttpClient::from_env() {
+        Ok(client) => Ok(Box::new(client)),
+        Err(AocClientError::MissingSession) if aoc_cli::check().is_ok() => Ok(Box::new(AocCli)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Reads the session cookie from the environment or from `~/.adventofcode.session`.
+fn read_session() -> Option<String> {
+    let session = match env::var(SESSION_ENV) {
+        Ok(session) => session,
+        Err(_) => {
+            let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+            fs::read_to_string(PathBuf::from(home).join(SESSION_FILE)).ok()?
+        }
+    };
+
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+pub fn get_input_path(puzzle: PuzzleId) -> String {
+    format!("data/{}/inputs/{}.txt", puzzle.year, puzzle.day)
+}
+
+pub fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    format!("data/{}/puzzles/{}.md", puzzle.year, puzzle.day)
+}
+
+fn write_data_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = PathBuf::from(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Talks to the Advent of Code website directly over HTTPS.
+pub struct HttpClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl HttpClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Creates a client for the Advent of Code website using the configured session cookie.
+    /// The base URL can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    fn url(&self, puzzle: PuzzleId, path: &str) -> String {
+        format!(
+            "{}/{}/day/{}{path}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Fetch the raw puzzle input.
+    pub fn fetch_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.url(puzzle, "/input"))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetch the puzzle description, converted to markdown.
+    pub fn fetch_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.url(puzzle, ""))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(html::articles_to_markdown(&response.into_string()?))
+    }
+
+    /// Post an answer, returning the server's reply converted to markdown.
+    pub fn post_answer(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&self.url(puzzle, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)])?;
+        Ok(html::articles_to_markdown(&response.into_string()?))
+    }
+}
+
+impl Backend for HttpClient {
+    fn download(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
+
+        write_data_file(&input_path, &self.fetch_input(puzzle)?)?;
+        write_data_file(&puzzle_path, &self.fetch_puzzle(puzzle)?)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        let description = self.fetch_puzzle(puzzle)?;
+        write_data_file(&get_puzzle_path(puzzle), &description)?;
+        println!("{description}");
+        Ok(())
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let message = self.post_answer(puzzle, part, answer)?;
+        println!("{message}");
+        Ok(message)
+    }
+}
+
+/// Delegates to the "aoc-cli" command-line.
+pub struct AocCli;
+
+impl Backend for AocCli {
+    fn download(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        aoc_cli::download(puzzle)?;
+        Ok(())
+    }
+
+    fn read(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        aoc_cli::read(puzzle)?;
+        Ok(())
+    }
+
+    fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let output = aoc_cli::submit(puzzle, part, answer)?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Minimal conversion of the puzzle pages to markdown, similar to what "aoc-cli" writes.
+mod html {
+    /// Convert the contents of every `<article>` element to markdown.
+    pub fn articles_to_markdown(page: &str) -> String {
+        let mut articles = vec![];
+        let mut rest = page;
+
+        while let Some(start) = rest.find("<article") {
+            let Some(end) = rest[start..].find("</article>") else {
+                break;
+            };
+            articles.push(to_markdown(&rest[start..start + end]));
+            rest = &rest[start + end + "</article>".len()..];
+        }
+
+        if articles.is_empty() {
+            return to_markdown(page);
+        }
+
+        articles.join("\n\n")
+    }
+
+    pub fn to_markdown(html: &str) -> String {
+        let mut out = String::new();
+        let mut in_pre = false;
+        let mut in_code = false;
+        let mut rest = html;
+
+        while let Some(start) = rest.find('<') {
+            out.push_str(&decode_entities(&rest[..start]));
+
+            let Some(end) = rest[start..].find('>') else {
+                rest = &rest[start..];
+                break;
+            };
+
+            let tag = &rest[start + 1..start + end];
+            rest = &rest[start + end + 1..];
+
+            let is_closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+
+            match (name.as_str(), is_closing) {
+                ("h2", false) => out.push_str("## "),
+                ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                ("code", closing) if !in_pre => {
+                    in_code = !closing;
+                    out.push('`');
+                }
+                ("em", _) if !in_pre && !in_code => out.push('*'),
+                ("li", false) => out.push_str("- "),
+                ("li" | "br", _) => out.push('\n'),
+                _ => {}
+            }
+        }
+
+        out.push_str(&decode_entities(rest));
+        out.trim().to_string()
+    }
+
+    fn decode_entities(s: &str) -> String {
+        s.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html, AocClientError, Backend, HttpClient};
+    use crate::puzzle;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serve a single request with the given body, returning the raw request that was received.
+    fn mock_server(body: &'static str) -> (String, JoinHandle<String>) {
+        mock_server_with_status("200 OK", body)
+    }
+
+    fn mock_server_with_status(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, server) = mock_server("1\n2\n3\n");
+        let client = HttpClient::new(&base_url, "secret");
+
+        let input = client.fetch_input(puzzle!(2016, 4)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1\n2\n3\n");
+        assert!(request.starts_with("GET /2016/day/4/input "));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn posts_answers() {
+        let (base_url, server) =
+            mock_server("<main><article><p>That's the right answer!</p></article></main>");
+        let client = HttpClient::new(&base_url, "secret");
+
+        let message = client.post_answer(puzzle!(2016, 4), 2, "1234").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(message, "That's the right answer!");
+        assert!(request.starts_with("POST /2016/day/4/answer "));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn fails_submission_on_error_status() {
+        let (base_url, server) = mock_server_with_status("400 Bad Request", "Puzzle inputs differ");
+        let client = HttpClient::new(&base_url, "expired");
+
+        let result = client.submit(puzzle!(2016, 4), 1, "1234");
+        server.join().unwrap();
+
+        assert!(matches!(result, Err(AocClientError::Status(400))));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let page = r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Find <em>the</em> answer to <code>a &amp; b</code>:</p><pre><code>1 &lt; 2
+<em>3</em>
+</code></pre></article></main>"#;
+
+        assert_eq!(
+            html::articles_to_markdown(page),
+            "## --- Day 1: Test ---\n\nFind *the* answer to `a & b`:\n\n```\n1 < 2\n3\n```"
+        );
+    }
+}
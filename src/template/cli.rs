@@ -0,0 +1,579 @@
+/// Describes the commands of the template CLI and the options of solution binaries.
+/// The descriptions drive `--help`, the validation of flags and the generated shell completions,
+/// while the values themselves are parsed with `pico_args` by the commands.
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// An option of a command, e.g. `--year <year>`.
+pub struct Flag {
+    pub name: &'static str,
+    /// Name of the value, if the option takes one. Optional values are written as `[name]`.
+    pub value: Option<&'static str>,
+    /// Accepted values, if the option only takes a fixed set of them.
+    pub choices: &'static [&'static str],
+    pub help: &'static str,
+}
+
+/// A command of the template CLI, e.g. `cargo solve`.
+pub struct CommandSpec {
+    pub name: &'static str,
+    /// Free arguments, e.g. `<day>`.
+    pub args: &'static str,
+    pub about: &'static str,
+    pub flags: &'static [Flag],
+}
+
+const fn flag(name: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: None,
+        choices: &[],
+        help,
+    }
+}
+
+const fn option(name: &'static str, value: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: Some(value),
+        choices: &[],
+        help,
+    }
+}
+
+const fn choice(
+    name: &'static str,
+    value: &'static str,
+    choices: &'static [&'static str],
+    help: &'static str,
+) -> Flag {
+    Flag {
+        name,
+        value: Some(value),
+        choices,
+        help,
+    }
+}
+
+const YEAR: Flag = option(
+    "--year",
+    "<year>",
+    "Year of the puzzles, defaults to the `AOC_YEAR` environment variable.",
+);
+const RELEASE: Flag = flag("--release", "Build the solutions in release mode.");
+const INPUT: Flag = option(
+    "--input",
+    "<path>",
+    "Read the input from a file instead, or from stdin with `-`.",
+);
+const EXAMPLE: Flag = option(
+    "--example",
+    "[name]",
+    "Read the example of the day instead, or a named example.",
+);
+const UNSOLVED: Flag = flag(
+    "--unsolved",
+    "Only select scaffolded days without an accepted answer for both parts.",
+);
+const SLOW: Flag = flag(
+    "--slow",
+    "Only select days whose last stored run took longer than a second.",
+);
+const IN_PROCESS: Flag = flag(
+    "--in-process",
+    "Run all days in a single process instead of one `cargo run` per day.",
+);
+const TIMEOUT: Flag = option(
+    "--timeout",
+    "<seconds>",
+    "Kill a day that runs longer and continue with the next one.",
+);
+const BENCH_BUDGET: Flag = option(
+    "--bench-budget",
+    "<ms>",
+    "Approximate time to spend benching each part, defaults to 1 second.",
+);
+const BENCH_ITERATIONS: Flag = option(
+    "--bench-iterations",
+    "<n>",
+    "Exact number of bench iterations, overrides the budget.",
+);
+
+/// Days of a multi-day command, e.g. `1-7,12`.
+const DAYS: &str = "[days]";
+
+/// Scaffold templates shipped in `src/templates`.
+pub const TEMPLATES: [&str; 4] = ["grid", "line_parser", "plain", "vm"];
+
+/// Shells that completions can be generated for.
+pub const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "scaffold",
+        args: "<day>",
+        about: "Create the solution, input and example files of a day.",
+        flags: &[
+            YEAR,
+            flag("--download", "Download the puzzle and its input first."),
+            flag("--overwrite", "Replace an existing solution file."),
+            choice(
+                "--template",
+                "<name>",
+                &TEMPLATES,
+                "Use a different solution template, defaults to `plain`.",
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "download",
+        args: "<day>",
+        about: "Download the puzzle description and input of a day.",
+        flags: &[YEAR],
+    },
+    CommandSpec {
+        name: "read",
+        args: "<day>",
+        about: "Print the puzzle description of a day.",
+        flags: &[YEAR],
+    },
+    CommandSpec {
+        name: "solve",
+        args: "<day>",
+        about: "Run the solution of a day.",
+        flags: &[
+            YEAR,
+            RELEASE,
+            flag(
+                "--dhat",
+                "Profile the heap with dhat, writing `dhat-heap.json`.",
+            ),
+            option("--submit", "<part>", "Submit the answer of part 1 or 2."),
+            INPUT,
+            EXAMPLE,
+        ],
+    },
+    CommandSpec {
+        name: "watch-day",
+        args: "<day>",
+        about: "Re-run the tests and the solution of a day when its files change.",
+        flags: &[YEAR, RELEASE, INPUT, EXAMPLE],
+    },
+    CommandSpec {
+        name: "all",
+        args: DAYS,
+        about: "Run the solutions of the selected days, or of all days.",
+        flags: &[
+            YEAR,
+            RELEASE,
+            IN_PROCESS,
+            option("--jobs", "<n>", "Number of days to run concurrently."),
+            TIMEOUT,
+            UNSOLVED,
+            SLOW,
+        ],
+    },
+    CommandSpec {
+        name: "time",
+        args: DAYS,
+        about: "Bench the selected days, or the days that are not fully benched yet.",
+        flags: &[
+            YEAR,
+            flag("--all", "Bench all days."),
+            flag("--store", "Store the timings and update the readme."),
+            option(
+                "--readme-extras",
+                "<list>",
+                "Optional parts of the readme table, e.g. `parse,chart`: parse, samples, spread, delta, memory, chart. Defaults to the `AOC_README_EXTRAS` environment variable.",
+            ),
+            IN_PROCESS,
+            flag(
+                "--memory",
+                "Measure the heap usage with dhat in a separate run.",
+            ),
+            TIMEOUT,
+            flag(
+                "--compare",
+                "Compare with the last stored run and fail on regressions.",
+            ),
+            option(
+                "--threshold",
+                "<percent>",
+                "Slowdown that counts as a regression, defaults to 10.",
+            ),
+            BENCH_BUDGET,
+            BENCH_ITERATIONS,
+            UNSOLVED,
+            SLOW,
+        ],
+    },
+    CommandSpec {
+        name: "verify",
+        args: DAYS,
+        about: "Check that the selected days still produce their accepted answers.",
+        flags: &[YEAR, UNSOLVED, SLOW],
+    },
+    CommandSpec {
+        name: "test-day",
+        args: DAYS,
+        about: "Run the tests of the selected days and summarize them.",
+        flags: &[
+            YEAR,
+            flag("--all", "Test all scaffolded days."),
+            RELEASE,
+            UNSOLVED,
+            SLOW,
+        ],
+    },
+    #[cfg(feature = "today")]
+    CommandSpec {
+        name: "today",
+        args: "",
+        about: "Download, scaffold and read the puzzle of today.",
+        flags: &[],
+    },
+    CommandSpec {
+        name: "completions",
+        args: "<bash|zsh|fish>",
+        about: "Print a completion script for the cargo aliases of this template.",
+        flags: &[],
+    },
+];
+
+/// Options of solution binaries. Parent commands pass them with [`SolutionArgs::to_args`].
+///
+/// [`SolutionArgs::to_args`]: crate::template::runner::SolutionArgs::to_args
+pub const SOLUTION: CommandSpec = CommandSpec {
+    name: "run --bin <year>_<day> --",
+    args: "",
+    about: "Run a solution. Usually invoked through `cargo solve`.",
+    flags: &[
+        flag("--json", "Print one machine-readable record per step."),
+        flag("--time", "Bench each part."),
+        BENCH_BUDGET,
+        BENCH_ITERATIONS,
+        option("--submit", "<part>", "Submit the answer of part 1 or 2."),
+        flag(
+            "--memory",
+            "Skip writing the dhat profile, only report the heap usage.",
+        ),
+        INPUT,
+        EXAMPLE,
+    ],
+};
+
+const HELP: Flag = Flag {
+    name: "-h, --help",
+    value: None,
+    choices: &[],
+    help: "Print this help.",
+};
+
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/// Whether the arguments ask for help.
+pub fn wants_help(args: &[String]) -> bool {
+    args.iter().any(|x| x == "-h" || x == "--help")
+}
+
+fn format_flags(out: &mut String, flags: &[&Flag]) {
+    // long flags are indented to line up with the long form of `-h, --help`.
+    let signature = |f: &Flag| {
+        let indent = if f.name.starts_with("--") { "    " } else { "" };
+        match f.value {
+            Some(value) => format!("{indent}{} {value}", f.name),
+            None => format!("{indent}{}", f.name),
+        }
+    };
+    let width = flags.iter().map(|f| signature(f).len()).max().unwrap_or(0);
+
+    for f in flags {
+        let _ = write!(out, "  {:width$}  {}", signature(f), f.help);
+        if !f.choices.is_empty() {
+            let _ = write!(out, " One of: {}.", f.choices.join(", "));
+        }
+        out.push('\n');
+    }
+}
+
+impl CommandSpec {
+    pub fn usage(&self) -> String {
+        let args = if self.args.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", self.args)
+        };
+        format!("cargo {}{args} [options]", self.name)
+    }
+
+    pub fn help(&self) -> String {
+        let mut out = format!("Usage: {}\n\n{}\n\nOptions:\n", self.usage(), self.about);
+        let flags: Vec<&Flag> = self.flags.iter().chain([&HELP]).collect();
+        format_flags(&mut out, &flags);
+        out
+    }
+
+    /// Check that every flag is known, before the values are parsed.
+    /// Values that look like flags, e.g. `-` for stdin, are accepted.
+    pub fn check_flags(&self, args: &[String]) -> Result<(), String> {
+        let is_flag = |x: &str| x.starts_with('-') && x.len() > 1;
+        let is_known =
+            |x: &str| x == "-h" || x == "--help" || self.flags.iter().any(|f| f.name == x);
+
+        match args.iter().find(|x| is_flag(x) && !is_known(x)) {
+            Some(unknown) => Err(format!(
+                "unknown option `{unknown}` for `cargo {}`. Run it with `--help` for usage.",
+                self.name
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Usage of the template CLI, listing all commands.
+pub fn usage() -> String {
+    let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut out = String::from("Usage: cargo <command> [options]\n\nCommands:\n");
+    for command in COMMANDS {
+        let _ = writeln!(out, "  {:width$}  {}", command.name, command.about);
+    }
+    out.push_str("\nRun `cargo <command> --help` for the options of a command.\n");
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A shell that completions can be generated for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!("unknown shell `{s}`, expecting one of {SHELLS:?}")),
+        }
+    }
+}
+
+fn flag_names(command: &CommandSpec) -> String {
+    command
+        .flags
+        .iter()
+        .map(|f| f.name)
+        .chain(["--help"])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Completions for the cargo aliases of the template, e.g. `cargo solve --rel<TAB>`.
+/// Other cargo subcommands are completed by cargo's own completion, if it is installed.
+pub fn completions(shell: Shell) -> String {
+    let mut out = String::new();
+
+    match shell {
+        Shell::Bash => {
+            let _ = writeln!(out, "_aoc_template() {{");
+            let _ = writeln!(out, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"");
+            let _ = writeln!(out, "    local prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"");
+            let _ = writeln!(out, "    case \"$prev\" in");
+            for f in COMMANDS.iter().flat_map(|c| c.flags) {
+                if !f.choices.is_empty() {
+                    let _ = writeln!(
+                        out,
+                        "        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;",
+                        f.name,
+                        f.choices.join(" ")
+                    );
+                }
+            }
+            let _ = writeln!(out, "    esac");
+            let _ = writeln!(out, "    case \"${{COMP_WORDS[1]}}\" in");
+            for command in COMMANDS {
+                let words = if command.name == "completions" {
+                    SHELLS.join(" ")
+                } else {
+                    flag_names(command)
+                };
+                let _ = writeln!(
+                    out,
+                    "        {}) COMPREPLY=($(compgen -W \"{words}\" -- \"$cur\")); return ;;",
+                    command.name
+                );
+            }
+            let _ = writeln!(out, "    esac");
+            let _ = writeln!(out, "    if declare -F _cargo >/dev/null; then");
+            let _ = writeln!(out, "        _cargo \"$@\"");
+            let _ = writeln!(out, "    elif [ \"$COMP_CWORD\" -eq 1 ]; then");
+            let names: Vec<&str> = COMMANDS.iter().map(|c| c.name).collect();
+            let _ = writeln!(
+                out,
+                "        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                names.join(" ")
+            );
+            let _ = writeln!(out, "    fi");
+            let _ = writeln!(out, "}}");
+            let _ = writeln!(out, "complete -F _aoc_template cargo");
+        }
+        Shell::Zsh => {
+            let _ = writeln!(out, "#compdef cargo");
+            let _ = writeln!(out, "_aoc_template() {{");
+            let _ = writeln!(out, "    case \"${{words[2]}}\" in");
+            for command in COMMANDS {
+                let _ = writeln!(out, "        {})", command.name);
+                if command.name == "completions" {
+                    let _ = writeln!(out, "            compadd {}", SHELLS.join(" "));
+                } else {
+                    // complete the options as if the alias was the command itself.
+                    let _ = writeln!(out, "            shift words; (( CURRENT-- ))");
+                    let _ = writeln!(out, "            _arguments \\");
+                    for f in command.flags {
+                        let help = f.help.replace('\'', "'\\''").replace(['[', ']'], "");
+                        let value = match f.value {
+                            Some(v) if !f.choices.is_empty() => {
+                                format!(
+                                    ":{}:({})",
+                                    v.trim_matches(&['<', '>'][..]),
+                                    f.choices.join(" ")
+                                )
+                            }
+                            Some(v) if v.starts_with('[') => {
+                                format!("::{}:", v.trim_matches(&['[', ']'][..]))
+                            }
+                            Some("<path>") => ":path:_files".into(),
+                            Some(v) => format!(":{}:", v.trim_matches(&['<', '>'][..])),
+                            None => String::new(),
+                        };
+                        let _ = writeln!(out, "                '{}[{help}]{value}' \\", f.name);
+                    }
+                    let _ = writeln!(out, "                '--help[Print the help.]'");
+                }
+                let _ = writeln!(out, "            ;;");
+            }
+            let _ = writeln!(out, "        *)");
+            let _ = writeln!(out, "            (( $+functions[_cargo] )) && _cargo");
+            let _ = writeln!(out, "            ;;");
+            let _ = writeln!(out, "    esac");
+            let _ = writeln!(out, "}}");
+            let _ = writeln!(out, "compdef _aoc_template cargo");
+        }
+        Shell::Fish => {
+            for command in COMMANDS {
+                let _ = writeln!(
+                    out,
+                    "complete -c cargo -n __fish_use_subcommand -a {} -d '{}'",
+                    command.name,
+                    command.about.replace('\'', "\\'")
+                );
+                let condition = format!("'__fish_seen_subcommand_from {}'", command.name);
+                if command.name == "completions" {
+                    let _ = writeln!(
+                        out,
+                        "complete -c cargo -n {condition} -f -a '{}'",
+                        SHELLS.join(" ")
+                    );
+                }
+                for f in command.flags {
+                    let requires_value = match f.value {
+                        _ if !f.choices.is_empty() => format!(" -x -a '{}'", f.choices.join(" ")),
+                        Some("<path>") => " -r -F".into(),
+                        Some(v) if v.starts_with('<') => " -r".into(),
+                        _ => String::new(),
+                    };
+                    let _ = writeln!(
+                        out,
+                        "complete -c cargo -n {condition} -l {}{requires_value} -d '{}'",
+                        f.name.trim_start_matches("--"),
+                        f.help.replace('\'', "\\'")
+                    );
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{completions, find_command, usage, Shell, COMMANDS, TEMPLATES};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn prints_help() {
+        let help = find_command("solve").unwrap().help();
+        assert!(help.starts_with("Usage: cargo solve <day> [options]\n"));
+        assert!(help
+            .lines()
+            .any(|line| line.starts_with("      --submit <part>")
+                && line.ends_with("Submit the answer of part 1 or 2.")));
+        assert!(help.contains("  -h, --help"));
+
+        assert!(usage().contains("  test-day  "));
+
+        let help = find_command("scaffold").unwrap().help();
+        assert!(help.contains("--template <name>"));
+        assert!(help.contains("One of: grid, line_parser, plain, vm."));
+    }
+
+    #[test]
+    fn lists_shipped_templates() {
+        let mut shipped: Vec<String> = std::fs::read_dir("src/templates")
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|x| x == "txt"))
+            .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+            .collect();
+        shipped.sort();
+        assert_eq!(shipped, TEMPLATES);
+    }
+
+    #[test]
+    fn checks_flags() {
+        let solve = find_command("solve").unwrap();
+        assert!(solve
+            .check_flags(&args(&["4", "--release", "--submit", "1"]))
+            .is_ok());
+        assert!(solve.check_flags(&args(&["4", "--help"])).is_ok());
+        assert!(solve.check_flags(&args(&["4", "--input", "-"])).is_ok());
+
+        let err = solve.check_flags(&args(&["4", "--relase"])).unwrap_err();
+        assert!(err.contains("`--relase`"));
+        assert!(solve.check_flags(&args(&["4", "-r"])).is_err());
+    }
+
+    #[test]
+    fn generates_completions() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completions(shell);
+            for command in COMMANDS {
+                assert!(script.contains(command.name), "{shell:?}: {}", command.name);
+            }
+        }
+
+        assert!(completions(Shell::Bash).contains("solve) COMPREPLY=($(compgen -W \"--year"));
+        assert!(completions(Shell::Fish)
+            .contains("complete -c cargo -n '__fish_seen_subcommand_from time' -l timeout -r"));
+        assert!(completions(Shell::Bash)
+            .contains("--template) COMPREPLY=($(compgen -W \"grid line_parser plain vm\""));
+        assert!(completions(Shell::Zsh).contains("]:name:(grid line_parser plain vm)'"));
+        assert!(completions(Shell::Fish).contains("-l template -x -a 'grid line_parser plain vm'"));
+        assert_eq!("fish".parse(), Ok(Shell::Fish));
+        assert!("tcsh".parse::<Shell>().is_err());
+    }
+}
@@ -1,5 +1,34 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    progress,
+    run_multi::{run_multi, RunOptions},
+    selection::DaySelection,
+    Year,
+};
+
+/// Run the selected days, or all days if none are selected.
+pub fn handle(
+    year: Year,
+    selection: &DaySelection,
+    is_release: bool,
+    in_process: bool,
+    jobs: Option<usize>,
+    timeout: Option<Duration>,
+) {
+    let days = selection.resolve(year);
+    if days.is_empty() {
+        eprintln!("No days match the selection.");
+        return;
+    }
+
+    let options = RunOptions {
+        is_release,
+        in_process,
+        jobs,
+        timeout,
+        ..RunOptions::default()
+    };
+    let results = run_multi(year, &days, options);
+    progress::update(&results.progress);
 }
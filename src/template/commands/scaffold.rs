@@ -1,13 +1,74 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::puzzle_description::PuzzleDescription;
+use crate::template::PuzzleId;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Directory with one `<name>.txt` file per scaffold template.
+const TEMPLATES_DIR: &str = "src/templates";
+
+pub const DEFAULT_TEMPLATE: &str = "plain";
+
+/// Puzzle details substituted into a template, where known.
+#[derive(Clone, Debug, Default)]
+pub struct TemplateValues {
+    pub title: Option<String>,
+    /// Expected answers for the example input as stated by the puzzle, e.g. `42` or `easter`.
+    pub part_one_answer: Option<String>,
+    pub part_two_answer: Option<String>,
+}
+
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "txt").then_some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn read_template(name: &str) -> Result<String, String> {
+    fs::read_to_string(Path::new(TEMPLATES_DIR).join(format!("{name}.txt"))).map_err(|_| {
+        format!(
+            "Unknown template `{name}`, available templates: {}.",
+            available_templates().join(", ")
+        )
+    })
+}
+
+/// Substitute the placeholders of a template:
+/// `%YEAR%`, `%DAY%` (e.g. `05`), `%DAY_NUMBER%` (e.g. `5`), `%TITLE%`, `%PART_ONE_ANSWER%` and `%PART_TWO_ANSWER%`.
+/// Unknown answers are substituted with `None`, matching the stubbed solution. Answers that don't fit
+/// its `Option<u32>`, e.g. strings or larger numbers, are kept in a comment next to `None`.
+fn render(template: &str, puzzle: PuzzleId, values: &TemplateValues) -> String {
+    let day_number = puzzle.day.into_inner();
+    let title = match &values.title {
+        Some(title) => format!("--- Day {day_number}: {title} ---"),
+        None => format!("--- Day {day_number} ---"),
+    };
+    let answer = |answer: &Option<String>| match answer {
+        Some(a) if a.parse::<u32>().is_ok() => format!("Some({a})"),
+        Some(a) if a.parse::<i128>().is_ok() => format!("None /* expected: Some({a}) */"),
+        Some(a) => format!("None /* expected: Some({a:?}) */"),
+        None => "None".into(),
+    };
+
+    template
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%DAY_NUMBER%", &day_number.to_string())
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%TITLE%", &title)
+        .replace("%PART_ONE_ANSWER%", &answer(&values.part_one_answer))
+        .replace("%PART_TWO_ANSWER%", &answer(&values.part_two_answer))
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,18 +80,42 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
+/// Create a file if it does not exist yet, e.g. an input that was downloaded before scaffolding.
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().append(true).create(true).open(path)
+}
+
+fn is_empty_file(path: &str) -> bool {
+    fs::metadata(path).map_or(true, |m| m.len() == 0)
+}
+
+/// Fill in template values from the downloaded puzzle description, if any.
+fn template_values(description: &PuzzleDescription) -> TemplateValues {
+    TemplateValues {
+        title: description.title.clone(),
+        part_one_answer: description.part_one_answer.clone(),
+        part_two_answer: description.part_two_answer.clone(),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, overwrite: bool, template: &str) {
+    let PuzzleId { year, day } = puzzle;
+    let input_path = format!("data/{year}/inputs/{day}.txt");
+    let example_path = format!("data/{year}/examples/{day}.txt");
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let description = PuzzleDescription::read(puzzle).unwrap_or_default();
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +125,9 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let values = template_values(&description);
+
+    match file.write_all(render(&template, puzzle, &values).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -55,6 +138,9 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
+        Ok(_) if !is_empty_file(&input_path) => {
+            println!("Kept existing input file \"{}\"", &input_path);
+        }
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
@@ -65,8 +151,21 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&example_path) {
+        Ok(mut file) if is_empty_file(&example_path) => match &description.example {
+            Some(example) => match file.write_all(example.as_bytes()) {
+                Ok(()) => println!(
+                    "Created example file \"{}\" from the puzzle description",
+                    &example_path
+                ),
+                Err(e) => {
+                    eprintln!("Failed to write example file: {e}");
+                    process::exit(1);
+                }
+            },
+            None => println!("Created empty example file \"{}\"", &example_path),
+        },
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Kept existing example file \"{}\"", &example_path);
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -75,5 +174,40 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, TemplateValues};
+    use crate::puzzle;
+
+    #[test]
+    fn renders_placeholders() {
+        let template = "// %YEAR%\n// %TITLE%\nsolution!(%YEAR%, %DAY_NUMBER%); // %DAY%\n%PART_ONE_ANSWER% %PART_TWO_ANSWER%";
+        let values = TemplateValues {
+            title: Some("Signals and Noise".into()),
+            part_one_answer: Some("6".into()),
+            part_two_answer: None,
+        };
+
+        assert_eq!(
+            render(template, puzzle!(2016, 6), &values),
+            "// 2016\n// --- Day 6: Signals and Noise ---\nsolution!(2016, 6); // 06\nSome(6) None"
+        );
+    }
+
+    #[test]
+    fn keeps_answers_that_do_not_fit_the_stub() {
+        let values = TemplateValues {
+            title: None,
+            part_one_answer: Some("easter".into()),
+            part_two_answer: Some("12345678901".into()),
+        };
+
+        assert_eq!(
+            render("// %TITLE%\n%PART_ONE_ANSWER%\n%PART_TWO_ANSWER%", puzzle!(2016, 6), &values),
+            "// --- Day 6 ---\nNone /* expected: Some(\"easter\") */\nNone /* expected: Some(12345678901) */"
+        );
+    }
 }
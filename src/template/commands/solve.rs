@@ -1,9 +1,13 @@
+use std::io;
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::input::InputSource;
+use crate::template::record::{parse_records, Record};
+use crate::template::runner::SolutionArgs;
+use crate::template::PuzzleId;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+fn cargo_args(puzzle: PuzzleId, release: bool, dhat: bool) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -17,11 +21,25 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args
+}
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: &InputSource,
+) {
+    let mut cmd_args = cargo_args(puzzle, release, dhat);
+    cmd_args.extend(
+        SolutionArgs {
+            submit: submit_part,
+            input: input.clone(),
+            ..SolutionArgs::default()
+        }
+        .to_args(),
+    );
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
@@ -32,3 +50,30 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Run a solution and collect its records instead of printing them. Build errors are still printed.
+pub fn capture(puzzle: PuzzleId, release: bool, input: &InputSource) -> io::Result<Vec<Record>> {
+    let mut cmd_args = cargo_args(puzzle, release, false);
+    cmd_args.insert(1, "--quiet".to_string());
+    cmd_args.extend(
+        SolutionArgs {
+            json: true,
+            input: input.clone(),
+            ..SolutionArgs::default()
+        }
+        .to_args(),
+    );
+
+    let output = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+
+    Ok(parse_records(&lines))
+}
@@ -0,0 +1,207 @@
+/// Runs the tests of scaffolded days with `cargo test` and summarizes them in a single table.
+use std::path::Path;
+use std::process::{self, Command};
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::selection::DaySelection;
+use crate::template::{all_days, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Test counts of a day, summed over all `test result` lines of its output.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TestSummary {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    /// Names of the failed tests, e.g. `tests::test_part_one`.
+    failures: Vec<String>,
+}
+
+impl TestSummary {
+    /// Parse the output of `cargo test`. Returns `None` if no tests ran, e.g. because the build failed.
+    fn parse(output: &str) -> Option<Self> {
+        let mut summary: Option<TestSummary> = None;
+
+        for line in output.lines() {
+            if let Some(counts) = line.strip_prefix("test result: ") {
+                let summary = summary.get_or_insert_with(TestSummary::default);
+                for count in counts.split(&['.', ';'][..]) {
+                    let mut words = count.split_whitespace();
+                    let (Some(n), Some(kind)) = (words.next(), words.next()) else {
+                        continue;
+                    };
+                    let Ok(n) = n.parse::<usize>() else {
+                        continue;
+                    };
+                    match kind {
+                        "passed" => summary.passed += n,
+                        "failed" => summary.failed += n,
+                        "ignored" => summary.ignored += n,
+                        _ => {}
+                    }
+                }
+            } else if let Some(name) = line
+                .strip_prefix("test ")
+                .and_then(|l| l.strip_suffix(" ... FAILED"))
+            {
+                summary
+                    .get_or_insert_with(TestSummary::default)
+                    .failures
+                    .push(name.to_string());
+            }
+        }
+
+        summary
+    }
+
+    fn is_success(&self) -> bool {
+        self.failed == 0 && self.failures.is_empty()
+    }
+}
+
+struct DayResult {
+    puzzle: PuzzleId,
+    summary: Option<TestSummary>,
+    output: String,
+}
+
+impl DayResult {
+    fn is_success(&self) -> bool {
+        self.summary.as_ref().is_some_and(TestSummary::is_success)
+    }
+}
+
+fn run_tests(puzzle: PuzzleId, is_release: bool) -> DayResult {
+    let mut args = vec!["test".to_string(), "--bin".to_string(), puzzle.to_string()];
+    if is_release {
+        args.push("--release".to_string());
+    }
+
+    let output = match Command::new("cargo").args(&args).output() {
+        Ok(output) => format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stderr),
+            String::from_utf8_lossy(&output.stdout)
+        ),
+        Err(e) => format!("Failed to run `cargo test`: {e}"),
+    };
+
+    DayResult {
+        puzzle,
+        summary: TestSummary::parse(&output),
+        output,
+    }
+}
+
+fn print_table(results: &[DayResult]) {
+    println!();
+    println!("{ANSI_BOLD}Day | Passed | Failed | Ignored | Status{ANSI_RESET}");
+
+    for result in results {
+        let day = result.puzzle.day;
+        match &result.summary {
+            Some(s) => println!(
+                " {day} | {:>6} | {:>6} | {:>7} | {}",
+                s.passed,
+                s.failed,
+                s.ignored,
+                if s.is_success() { "✔" } else { "✖" }
+            ),
+            None => println!(" {day} |      - |      - |       - | ✖ build failed"),
+        }
+    }
+}
+
+/// Test the selected days, or all scaffolded days of a year if none are selected.
+pub fn handle(year: Year, selection: &DaySelection, is_release: bool) {
+    let days = selection.resolve(year);
+    let puzzles: Vec<PuzzleId> = all_days()
+        .filter(|d| days.contains(d))
+        .map(|d| PuzzleId::new(year, d))
+        .filter(|p| Path::new(&get_path_for_bin(*p)).exists())
+        .collect();
+
+    if puzzles.is_empty() {
+        eprintln!("No scaffolded days to test.");
+        process::exit(1);
+    }
+
+    let results: Vec<DayResult> = puzzles
+        .into_iter()
+        .map(|puzzle| {
+            println!("Testing {puzzle}...");
+            run_tests(puzzle, is_release)
+        })
+        .collect();
+
+    // show the full output of failing days, so they can be fixed without re-running.
+    for result in results.iter().filter(|r| !r.is_success()) {
+        println!();
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", result.puzzle.day);
+        println!("------");
+        println!("{}", result.output.trim_end());
+    }
+
+    print_table(&results);
+
+    let failing = results.iter().filter(|r| !r.is_success()).count();
+    println!();
+
+    if failing > 0 {
+        for result in results.iter().filter(|r| !r.is_success()) {
+            for name in result.summary.iter().flat_map(|s| &s.failures) {
+                eprintln!("Day {}: {name} failed.", result.puzzle.day);
+            }
+        }
+        eprintln!("{failing} day(s) failed. Re-run one with `cargo test --bin <year>_<day>`.");
+        process::exit(1);
+    }
+
+    println!("🎄 All tests passed.");
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::TestSummary;
+
+    #[test]
+    fn parses_test_output() {
+        let output = "
+running 3 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... FAILED
+test tests::test_slow ... ignored
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+        assert_eq!(
+            TestSummary::parse(output),
+            Some(TestSummary {
+                passed: 1,
+                failed: 1,
+                ignored: 1,
+                failures: vec!["tests::test_part_two".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn handles_build_failures() {
+        let output = "error[E0308]: mismatched types\nerror: could not compile `advent_of_code`";
+        assert_eq!(TestSummary::parse(output), None);
+    }
+
+    #[test]
+    fn sums_test_results() {
+        let output = "test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n\
+                      test result: ok. 3 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out";
+        let summary = TestSummary::parse(output).unwrap();
+        assert_eq!(summary.passed, 5);
+        assert_eq!(summary.ignored, 1);
+        assert!(summary.is_success());
+    }
+}
@@ -1,34 +1,175 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::record::Step;
+use crate::template::run_multi::{child_commands, run_multi, RunOptions};
+use crate::template::runner::BenchConfig;
+use crate::template::selection::DaySelection;
+use crate::template::timings::{Comparison, Timings};
+use crate::template::{
+    all_days, progress, readme_benchmarks, PuzzleId, Year, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub use crate::template::readme_benchmarks::TableOptions;
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
+/// Print how each day changed compared to its last stored run. Returns the number of regressions.
+fn print_comparisons(comparisons: &[Comparison], threshold: f64) -> usize {
+    println!();
+    println!(
+        "{ANSI_BOLD}Compared to last stored run (threshold {:.0}%){ANSI_RESET}",
+        threshold * 100.0
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if comparisons.is_empty() {
+        println!("No stored timings to compare with.");
+        return 0;
+    }
+
+    let to_duration = |nanos: f64| Duration::from_secs_f64(nanos / 1e9);
+
+    for comparison in comparisons {
+        let verdict = if comparison.is_regression(threshold) {
+            format!("{ANSI_BOLD}slower{ANSI_RESET}")
+        } else {
+            "ok".into()
+        };
+
+        println!(
+            "Day {}: {:.1?} -> {:.1?} ({:+.1}%, vs. {}) {verdict}",
+            comparison.puzzle.day,
+            to_duration(comparison.baseline_nanos),
+            to_duration(comparison.current_nanos),
+            comparison.change() * 100.0,
+            comparison
+                .baseline_commit
+                .as_deref()
+                .unwrap_or("unknown commit"),
+        );
+    }
+
+    comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count()
+}
+
+/// Measure the heap usage of each timed day and add it to its timing.
+fn measure_memory(timings: &mut Timings) {
+    println!();
+    println!("{ANSI_BOLD}Memory{ANSI_RESET}");
+
+    // a day that timed out would run just as long again.
+    for timing in timings.data.iter_mut().filter(|t| !t.timed_out) {
+        let records = match child_commands::measure_memory(timing.puzzle) {
+            Ok(records) => records,
+            Err(e) => {
+                eprintln!("Day {}: failed to measure memory: {e:?}", timing.puzzle.day);
+                continue;
+            }
+        };
+
+        for record in records {
+            match record.step {
+                Step::Parse => timing.parse_memory = record.memory,
+                Step::Part(1) => timing.part_1_memory = record.memory,
+                Step::Part(_) => timing.part_2_memory = record.memory,
+            }
+        }
+
+        match timing.memory() {
+            Some(memory) => println!("Day {}: {memory}", timing.puzzle.day),
+            None => println!("Day {}: not measured", timing.puzzle.day),
+        }
+    }
+}
+
+/// Options of `cargo time`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TimeOptions {
+    /// Bench all days instead of only the ones that are not fully benched, with `--all`.
+    pub run_all: bool,
+    /// Store the timings and update the readme with these optional parts, with `--store`.
+    pub store: Option<TableOptions>,
+    pub bench: BenchConfig,
+    /// Run all days in a single process, with `--in-process`.
+    pub in_process: bool,
+    /// Regression threshold with `--compare`, e.g. `0.1` for 10%.
+    pub compare: Option<f64>,
+    /// Measure the heap usage with dhat, with `--memory`.
+    pub memory: bool,
+    /// Kill a day that runs longer, with `--timeout <seconds>`.
+    pub timeout: Option<Duration>,
+}
+
+/// Bench the selected days. Without a selection, only days that are not fully benched run, or all days
+/// with `run_all`. With `compare` set to a threshold, days that got slower than their last stored run
+/// are flagged and the command fails.
+/// Each run counts allocations with the `count-allocs` feature. With `memory`, the heap usage is
+/// measured more precisely with dhat in a separate run.
+/// Days that run longer than `timeout` are killed and marked as timed out.
+pub fn handle(year: Year, selection: &DaySelection, options: TimeOptions) {
+    let TimeOptions {
+        run_all,
+        store,
+        bench,
+        in_process,
+        compare,
+        memory,
+        timeout,
+    } = options;
+    let stored_timings = Timings::read_from_file();
+
+    let days_to_run: HashSet<_> = if !selection.is_empty() {
+        selection.resolve(year)
+    } else if compare.is_some() {
+        // only days with a stored run can be compared.
+        stored_timings
+            .data
+            .iter()
+            .filter(|t| t.puzzle.year == year)
+            .map(|t| t.puzzle.day)
+            .collect()
+    } else if run_all {
+        all_days().collect()
+    } else {
+        // when the `--all` flag is not set, filter out days that are fully benched.
+        all_days()
+            .filter(|day| !stored_timings.is_day_complete(PuzzleId::new(year, *day)))
+            .collect()
+    };
 
-    if store {
+    if days_to_run.is_empty() && !selection.is_empty() {
+        eprintln!("No days match the selection.");
+        return;
+    }
+
+    let options = RunOptions {
+        is_release: true,
+        bench: Some(bench),
+        in_process,
+        timeout,
+        ..RunOptions::default()
+    };
+    let results = run_multi(year, &days_to_run, options);
+    progress::update(&results.progress);
+    let mut timings = results.timings.unwrap();
+
+    if memory {
+        measure_memory(&mut timings);
+    }
+
+    let regressions = compare.map_or(0, |threshold| {
+        print_comparisons(&timings.compare(&stored_timings), threshold)
+    });
+
+    if let Some(table_options) = store {
+        timings.stamp();
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, table_options) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +178,9 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if regressions > 0 {
+        eprintln!("{regressions} day(s) got slower than their last stored run.");
+        process::exit(1);
+    }
 }
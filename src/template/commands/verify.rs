@@ -0,0 +1,121 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::child_commands;
+use crate::template::selection::DaySelection;
+use crate::template::{all_days, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Outcome of checking one part against the answer ledger.
+enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// No accepted answer recorded yet, or a multi-line answer that was read by a human.
+    Unknown,
+}
+
+impl Status {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Status::Pass => "✔",
+            Status::Fail { .. } => "✖",
+            Status::Unknown => "-",
+        }
+    }
+}
+
+fn check_part(answers: &Answers, puzzle: PuzzleId, part: u8, output: &[String]) -> Status {
+    let Some(expected) = answers.correct_answer(puzzle, part) else {
+        return Status::Unknown;
+    };
+
+    let actual = child_commands::parse_answer(output, part);
+
+    if actual.as_ref().is_some_and(|a| a.contains('\n')) {
+        return Status::Unknown;
+    }
+
+    if actual.as_deref() == Some(expected) {
+        Status::Pass
+    } else {
+        Status::Fail {
+            expected: expected.to_string(),
+            actual,
+        }
+    }
+}
+
+pub fn handle(year: Year, selection: &DaySelection) {
+    let answers = Answers::read_from_file();
+    let mut results: Vec<(PuzzleId, [Status; 2])> = vec![];
+    let mut errors = 0;
+    let days = selection.resolve(year);
+
+    for day in all_days().filter(|d| days.contains(d)) {
+        let puzzle = PuzzleId::new(year, day);
+        let output = match child_commands::run_solution(puzzle, None, true, None) {
+            Ok(output) => output.stdout,
+            Err(e) => {
+                eprintln!("Day {day}: failed to run the solution: {e:?}");
+                errors += 1;
+                continue;
+            }
+        };
+
+        // skip days that have not been scaffolded yet.
+        if output.is_empty() {
+            continue;
+        }
+
+        results.push((
+            puzzle,
+            [
+                check_part(&answers, puzzle, 1, &output),
+                check_part(&answers, puzzle, 2, &output),
+            ],
+        ));
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Day | Part 1 | Part 2{ANSI_RESET}");
+    for (puzzle, [part_1, part_2]) in &results {
+        println!(
+            " {} |   {}    |   {}",
+            puzzle.day,
+            part_1.symbol(),
+            part_2.symbol()
+        );
+    }
+
+    let mut regressions = 0;
+    println!();
+
+    for (puzzle, statuses) in &results {
+        for (part, status) in (1..).zip(statuses) {
+            if let Status::Fail { expected, actual } = status {
+                regressions += 1;
+                eprintln!(
+                    "Day {} part {part}: expected `{expected}`, got `{}`.",
+                    puzzle.day,
+                    actual.as_deref().unwrap_or("✖")
+                );
+            }
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!("{regressions} part(s) no longer produce the accepted answer.");
+    }
+
+    if errors > 0 {
+        eprintln!("{errors} day(s) could not be run.");
+    }
+
+    if regressions > 0 || errors > 0 {
+        process::exit(1);
+    }
+
+    println!("🎄 All recorded answers verified.");
+}
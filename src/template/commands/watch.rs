@@ -0,0 +1,183 @@
+/// Re-runs the tests and the solution of a day whenever its source, examples or input change.
+/// Changes are detected by polling modification times, so no file watcher is needed.
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use crate::template::aoc_client::get_input_path;
+use crate::template::commands::solve;
+use crate::template::input::InputSource;
+use crate::template::record::{Record, Step};
+use crate::template::runner::print_record;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors often write a file in several steps, wait for them to finish before running.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Files that trigger a run when they change.
+fn watched_paths(puzzle: PuzzleId, input: &InputSource) -> Vec<PathBuf> {
+    let examples = format!("data/{}/examples/{}", puzzle.year, puzzle.day);
+
+    let mut paths = vec![
+        PathBuf::from(format!("src/bin/{puzzle}.rs")),
+        PathBuf::from(format!("{examples}.txt")),
+        PathBuf::from(get_input_path(puzzle)),
+    ];
+
+    // named examples, see `template::examples`.
+    if let Ok(entries) = fs::read_dir(&examples) {
+        paths.extend(entries.filter_map(|e| e.ok().map(|e| e.path())));
+    }
+
+    if let InputSource::File(path) = input {
+        paths.push(PathBuf::from(path));
+    }
+
+    paths
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn snapshot(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    paths.iter().map(|p| (p.clone(), modified(p))).collect()
+}
+
+fn run_tests(puzzle: PuzzleId) -> bool {
+    println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+    println!("------");
+
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &puzzle.to_string()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn answer_of(records: &[Record], part: u8) -> Option<&str> {
+    records
+        .iter()
+        .find(|r| r.step == Step::Part(part))
+        .and_then(|r| r.answer.as_deref())
+}
+
+/// Describe how the answers changed since the previous run, one line per changed part.
+fn describe_changes(previous: &[Record], current: &[Record]) -> Vec<String> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let before = answer_of(previous, part);
+            let after = answer_of(current, part);
+            (before != after).then(|| {
+                format!(
+                    "Part {part}: {} → {}",
+                    before.unwrap_or("✖"),
+                    after.unwrap_or("✖")
+                )
+            })
+        })
+        .collect()
+}
+
+fn run(puzzle: PuzzleId, release: bool, input: &InputSource, previous: &[Record]) -> Vec<Record> {
+    print!("{CLEAR_SCREEN}");
+    println!("{ANSI_ITALIC}Watching {puzzle} for changes, press Ctrl-C to stop.{ANSI_RESET}");
+    println!();
+
+    let tests_passed = run_tests(puzzle);
+    if tests_passed {
+        println!("All tests passed.");
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Solution ({}){ANSI_RESET}", input.label());
+    println!("------");
+
+    let records = match solve::capture(puzzle, release, input) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Failed to run the solution: {e}");
+            return previous.to_vec();
+        }
+    };
+
+    records.iter().for_each(print_record);
+
+    // a build error yields no records, keep the last answers to compare with the next run.
+    if records.is_empty() {
+        return previous.to_vec();
+    }
+
+    if !previous.is_empty() {
+        let changes = describe_changes(previous, &records);
+        println!();
+        if changes.is_empty() {
+            println!("{ANSI_ITALIC}Answers unchanged since the last run.{ANSI_RESET}");
+        } else {
+            println!("{ANSI_BOLD}Changed since the last run:{ANSI_RESET}");
+            changes.iter().for_each(|c| println!("{c}"));
+        }
+    }
+
+    records
+}
+
+pub fn handle(puzzle: PuzzleId, release: bool, input: &InputSource) {
+    if *input == InputSource::File("-".into()) {
+        eprintln!("Reading the input from stdin is not supported in watch mode.");
+        process::exit(1);
+    }
+
+    let mut records = run(puzzle, release, input, &[]);
+    let mut state = snapshot(&watched_paths(puzzle, input));
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        // re-list the paths, named examples may have been added.
+        let current = snapshot(&watched_paths(puzzle, input));
+        if current == state {
+            continue;
+        }
+
+        thread::sleep(SETTLE_TIME);
+        records = run(puzzle, release, input, &records);
+        state = snapshot(&watched_paths(puzzle, input));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::describe_changes;
+    use crate::template::record::{Record, Step};
+    use crate::template::timings::Stats;
+    use std::time::Duration;
+
+    fn records(part_1: Option<&str>, part_2: Option<&str>) -> Vec<Record> {
+        let stats = Stats::from_durations(&[Duration::from_nanos(10)]);
+        vec![
+            Record::new(Step::Part(1), part_1.map(str::to_string), stats.clone()),
+            Record::new(Step::Part(2), part_2.map(str::to_string), stats),
+        ]
+    }
+
+    #[test]
+    fn describes_changed_answers() {
+        let previous = records(Some("42"), None);
+
+        assert!(describe_changes(&previous, &records(Some("42"), None)).is_empty());
+        assert_eq!(
+            describe_changes(&previous, &records(Some("43"), Some("7"))),
+            vec!["Part 1: 42 → 43", "Part 2: ✖ → 7"]
+        );
+    }
+}
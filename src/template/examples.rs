@@ -0,0 +1,213 @@
+/// Named examples of a day, stored as `data/2016/examples/09/<name>.txt` next to a manifest
+/// `expected.json` that maps each name to the expected answer of each part, e.g.
+/// `{ "nested": { "part_1": "6", "part_2": "3" } }`. A part without an expectation is not tested.
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{env, fs};
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+
+static MANIFEST_FILE_NAME: &str = "expected.json";
+
+/// The expected answers of a single example.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expectation {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Expectation {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+}
+
+/// The expectations manifest of a day, keyed by example name.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    pub data: HashMap<String, Expectation>,
+}
+
+impl Manifest {
+    /// Read the manifest of a day. Panics if it is missing or malformed, as it's only used by tests.
+    pub fn read(puzzle: PuzzleId) -> Self {
+        let path = examples_dir(puzzle).join(MANIFEST_FILE_NAME);
+        let s = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not open {}: {e}", path.display()));
+        Manifest::try_from(s).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+    }
+
+    /// Example names in alphabetical order.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.data.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn expected(&self, name: &str, part: u8) -> Option<&str> {
+        self.data.get(name).and_then(|e| e.part(part))
+    }
+}
+
+/// Directory holding the named examples of a day, e.g. `data/2016/examples/09`.
+pub fn examples_dir(puzzle: PuzzleId) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join(puzzle.year.to_string())
+        .join("examples")
+        .join(puzzle.day.to_string())
+}
+
+/// Read a named example, e.g. `data/2016/examples/09/nested.txt`.
+#[must_use]
+pub fn read_example(puzzle: PuzzleId, name: &str) -> String {
+    let path = examples_dir(puzzle).join(format!("{name}.txt"));
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("could not open {}: {e}", path.display()))
+}
+
+/// Generates one `#[test]` per named example and part, checking the answer against the manifest.
+/// Tests are grouped in a module named after the part function, e.g. `tests::part_one::nested`.
+///
+/// An additional `manifest_is_covered` test fails if the manifest lists examples that are not tested.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     advent_of_code::example_tests!(PUZZLE, [advent, nested], part_one = 1, part_two = 2);
+/// }
+/// ```
+///
+/// Solutions with a `parse` step pass it first, e.g. `example_tests!(PUZZLE, parse = parse_input, [..], ..)`.
+#[macro_export]
+macro_rules! example_tests {
+    ($puzzle:expr, [$($name:ident),* $(,)?], $($func:ident = $part:expr),+ $(,)?) => {
+        $crate::example_tests!(
+            $puzzle,
+            parse = |input: &str| input.to_string(),
+            [$($name),*],
+            $($func = $part),+
+        );
+    };
+    ($puzzle:expr, parse = $parse:expr, $names:tt, $($func:ident = $part:expr),+ $(,)?) => {
+        $( $crate::example_tests!(@part $puzzle, $parse, $names, $func, $part); )+
+        $crate::example_tests!(@covered $puzzle, $names);
+    };
+
+    (@covered $puzzle:expr, [$($name:ident),* $(,)?]) => {
+        #[test]
+        fn manifest_is_covered() {
+            let manifest = $crate::template::examples::Manifest::read($puzzle);
+            let tested = [$(stringify!($name)),*];
+            let missing: Vec<&str> = manifest
+                .names()
+                .into_iter()
+                .filter(|name| !tested.contains(name))
+                .collect();
+            assert!(missing.is_empty(), "examples without a test: {missing:?}");
+        }
+    };
+
+    (@part $puzzle:expr, $parse:expr, [$($name:ident),* $(,)?], $func:ident, $part:expr) => {
+        mod $func {
+            use super::*;
+
+            $(
+                #[test]
+                fn $name() {
+                    let manifest = $crate::template::examples::Manifest::read($puzzle);
+                    let name = stringify!($name);
+                    let Some(expected) = manifest.expected(name, $part) else {
+                        return;
+                    };
+                    let input = $crate::template::examples::read_example($puzzle, name);
+                    let input = ($parse)(input.as_str());
+                    let result = $func(&input);
+                    let answer = $crate::template::runner::PartResult::answer(&result);
+                    let error = $crate::template::runner::PartResult::error(&result);
+                    assert_eq!(error, None, "example `{name}` failed");
+                    assert_eq!(answer.as_deref(), Some(expected), "example `{name}`");
+                }
+            )*
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(Manifest {
+            data: json_data
+                .iter()
+                .map(|(name, value)| Ok((name.clone(), Expectation::try_from(value)?)))
+                .collect::<Result<_, String>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Expectation {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        // answers may be written as strings or as plain numbers.
+        let answer = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(JsonValue::Number(n)) => Ok(Some(n.to_string())),
+            Some(_) => Err(format!("Expected example.{key} to be a string or number.")),
+        };
+
+        Ok(Expectation {
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Manifest;
+
+    #[test]
+    fn parses_manifest() {
+        let manifest = Manifest::try_from(
+            r#"{ "nested": { "part_1": "6", "part_2": 3 }, "long": { "part_2": "241920" } }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(manifest.names(), vec!["long", "nested"]);
+        assert_eq!(manifest.expected("nested", 1), Some("6"));
+        assert_eq!(manifest.expected("nested", 2), Some("3"));
+        assert_eq!(manifest.expected("long", 1), None);
+        assert_eq!(manifest.expected("missing", 1), None);
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert!(Manifest::try_from(r#"{ "a": { "part_1": [] } }"#.to_string()).is_err());
+        assert!(Manifest::try_from(r#"["a"]"#.to_string()).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+/// Runs the solutions of a year in a single process instead of one `cargo run` per day.
+/// Solutions are linked into the `aggregate` binary through a registry that `build.rs` generates
+/// from `src/bin/`. The binary prints one record per step, tagged with its puzzle, in day order.
+use std::any::Any;
+use std::collections::HashSet;
+use std::io::{stdout, Write};
+use std::time::Duration;
+use std::{panic, process, thread};
+
+use crate::template::record::{Record, Step};
+use crate::template::run_multi::run_ordered;
+use crate::template::runner::BenchConfig;
+use crate::template::timings::Stats;
+use crate::template::{try_read_file, Day, PuzzleId, ReadError, Year};
+
+/// Runs a solution on the given input without printing, see [`crate::solution`].
+pub type SolveFn = fn(&str, Option<BenchConfig>) -> Vec<Record>;
+
+/// A solution linked into the aggregated binary.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub solve: SolveFn,
+}
+
+struct Options {
+    year: Year,
+    days: HashSet<Day>,
+    jobs: usize,
+    bench: Option<BenchConfig>,
+}
+
+fn parse_days(s: &str) -> Result<HashSet<Day>, String> {
+    s.split(',')
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse().map_err(|e| format!("{e}")))
+        .collect()
+}
+
+fn parse_args() -> Result<Options, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let time = args.contains("--time");
+    let bench = BenchConfig::from_args(&mut args)?;
+    let bench = time.then_some(bench);
+
+    // parallel runs would skew each other's timings, only bench one day at a time.
+    let default_jobs = match bench {
+        Some(_) => 1,
+        None => thread::available_parallelism().map_or(1, usize::from),
+    };
+
+    Ok(Options {
+        year: args.value_from_str("--year")?,
+        days: args.value_from_fn("--days", parse_days)?,
+        jobs: args
+            .opt_value_from_str("--jobs")?
+            .unwrap_or(default_jobs)
+            .max(1),
+        bench,
+    })
+}
+
+/// Entry point of the aggregated binary.
+pub fn main(solutions: &[Solution]) {
+    let options = parse_args().unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+
+    let selected: Vec<&Solution> = solutions
+        .iter()
+        .filter(|s| s.puzzle.year == options.year && options.days.contains(&s.puzzle.day))
+        .collect();
+
+    run_ordered(
+        &selected,
+        options.jobs,
+        |solution| run_solution(solution, options.bench),
+        |_, records| {
+            let mut stdout = stdout().lock();
+            for record in records {
+                let _ = writeln!(stdout, "{}", record.to_line());
+            }
+            let _ = stdout.flush();
+        },
+    );
+}
+
+/// Run a single solution on its input. A panic fails both parts instead of aborting the other days.
+fn run_solution(solution: &Solution, bench: Option<BenchConfig>) -> Vec<Record> {
+    let puzzle = solution.puzzle;
+
+    let records = match try_read_file("inputs", puzzle) {
+        Ok(input) => {
+            panic::catch_unwind(|| (solution.solve)(&input, bench)).unwrap_or_else(|payload| {
+                let message = panic_message(payload.as_ref());
+                failed_records(&format!("{puzzle} panicked: {message}"))
+            })
+        }
+        // like a missing binary, a missing input means the day is not solved yet.
+        Err(ReadError::Missing { .. }) => return vec![],
+        Err(e) => failed_records(&e.to_string()),
+    };
+
+    records
+        .into_iter()
+        .map(|mut record| {
+            record.puzzle = Some(puzzle);
+            record
+        })
+        .collect()
+}
+
+fn failed_records(message: &str) -> Vec<Record> {
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            let stats = Stats::from_durations(&[Duration::ZERO]);
+            let mut record = Record::new(Step::Part(part), None, stats);
+            record.error = Some(message.to_string());
+            record
+        })
+        .collect()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown error"
+    }
+}
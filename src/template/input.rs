@@ -0,0 +1,178 @@
+/// Selects the input of a solution binary. By default, solutions read the downloaded input of their puzzle,
+/// which can be overridden with `--input <path>` or `--example [name]`, e.g. to try an edge case.
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process;
+
+use crate::template::runner::SolutionArgs;
+use crate::template::{
+    data_path, normalize_input, read_path, try_read_file, PuzzleId, ReadError, ANSI_ITALIC,
+    ANSI_RESET,
+};
+
+/// Where a solution reads its input from. Passed to solution binaries as `--input <path>` or `--example [name]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The downloaded input, e.g. `data/2016/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// A file passed with `--input <path>`, or stdin with `--input -`.
+    File(String),
+    /// The example of the day, e.g. `data/2016/examples/09.txt`, or a named example
+    /// like `data/2016/examples/09/nested.txt`.
+    Example(Option<String>),
+}
+
+impl InputSource {
+    /// Parse and remove the input options from a list of arguments.
+    /// The name of `--example` is optional. It is told apart from a day number by not being numeric.
+    pub fn take_from_args(args: &mut Vec<String>) -> Result<Self, String> {
+        let take_option = |args: &mut Vec<String>, name: &str| {
+            let index = args.iter().position(|x| x == name)?;
+            args.remove(index);
+            let value = args
+                .get(index)
+                .filter(|v| !v.starts_with("--") && !v.chars().all(|c| c.is_ascii_digit()))
+                .cloned();
+            if value.is_some() {
+                args.remove(index);
+            }
+            Some(value)
+        };
+
+        let input = take_option(args, "--input");
+        let example = take_option(args, "--example");
+
+        match (input, example) {
+            (Some(_), Some(_)) => Err("pass either --input or --example, not both.".into()),
+            (Some(None), None) => {
+                Err("missing value for --input. Pass a path, or `-` to read from stdin.".into())
+            }
+            (Some(Some(path)), None) => Ok(InputSource::File(path)),
+            (None, Some(name)) => Ok(InputSource::Example(name)),
+            (None, None) => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// Arguments that mirror this source to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::File(path) => vec!["--input".into(), path.clone()],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(name)) => vec!["--example".into(), name.clone()],
+        }
+    }
+
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// Describes the input for the runner output, e.g. `example "nested"`.
+    pub fn label(&self) -> String {
+        match self {
+            InputSource::Puzzle => "puzzle input".into(),
+            InputSource::File(path) if path == "-" => "stdin".into(),
+            InputSource::File(path) => format!("\"{path}\""),
+            InputSource::Example(None) => "example".into(),
+            InputSource::Example(Some(name)) => format!("example \"{name}\""),
+        }
+    }
+
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, ReadError> {
+        match self {
+            InputSource::Puzzle => try_read_file("inputs", puzzle),
+            InputSource::Example(None) => try_read_file("examples", puzzle),
+            InputSource::Example(Some(name)) => {
+                let file_name = format!("{}/{name}.txt", puzzle.day);
+                read_path(data_path("examples", puzzle, &file_name), || {
+                    "Check the name passed with --example.".into()
+                })
+            }
+            InputSource::File(path) if path == "-" => {
+                let mut s = String::new();
+                match io::stdin().read_to_string(&mut s) {
+                    Ok(_) => Ok(normalize_input(&s)),
+                    Err(source) => Err(ReadError::Io {
+                        path: PathBuf::from("<stdin>"),
+                        source,
+                    }),
+                }
+            }
+            InputSource::File(path) => read_path(PathBuf::from(path), || {
+                "Check the path passed with --input.".into()
+            }),
+        }
+    }
+}
+
+/// Reads the input of a solution binary, see [`InputSource`]. Overridden inputs are labeled in the output.
+/// Exits with the reason if the input can't be read.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let SolutionArgs {
+        json,
+        input: source,
+        ..
+    } = SolutionArgs::from_env();
+
+    let input = source.read(puzzle).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    if !source.is_puzzle() && !json {
+        println!("{ANSI_ITALIC}Input: {}{ANSI_RESET}", source.label());
+    }
+
+    input
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    fn take(args: &[&str]) -> (Result<InputSource, String>, Vec<String>) {
+        let mut args: Vec<String> = args.iter().map(|x| x.to_string()).collect();
+        let source = InputSource::take_from_args(&mut args);
+        (source, args)
+    }
+
+    #[test]
+    fn parses_input_options() {
+        let (source, rest) = take(&["solve", "9", "--input", "edge.txt", "--release"]);
+        assert_eq!(source, Ok(InputSource::File("edge.txt".into())));
+        assert_eq!(rest, vec!["solve", "9", "--release"]);
+
+        let (source, rest) = take(&["solve", "9", "--example", "nested"]);
+        assert_eq!(source, Ok(InputSource::Example(Some("nested".into()))));
+        assert_eq!(rest, vec!["solve", "9"]);
+
+        let (source, rest) = take(&["solve", "--example", "9", "--release"]);
+        assert_eq!(source, Ok(InputSource::Example(None)));
+        assert_eq!(rest, vec!["solve", "9", "--release"]);
+
+        let (source, _) = take(&["solve", "9"]);
+        assert_eq!(source, Ok(InputSource::Puzzle));
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        assert!(take(&["--input"]).0.is_err());
+        assert!(take(&["--input", "a.txt", "--example"]).0.is_err());
+    }
+
+    #[test]
+    fn mirrors_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::File("-".into()),
+            InputSource::Example(None),
+            InputSource::Example(Some("nested".into())),
+        ] {
+            let mut args = source.to_args();
+            assert_eq!(InputSource::take_from_args(&mut args), Ok(source));
+        }
+    }
+}
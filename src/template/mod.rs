@@ -1,68 +1,213 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod cli;
 pub mod commands;
+pub mod examples;
+pub mod in_process;
+pub mod input;
+pub mod record;
 pub mod runner;
+pub mod selection;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
 mod day;
+mod progress;
+mod puzzle_description;
 mod readme_benchmarks;
+mod readme_progress;
 mod run_multi;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Errors when reading an input or example file, with a hint on how to fix them.
+#[derive(Debug)]
+pub enum ReadError {
+    Missing { path: PathBuf, hint: String },
+    Empty { path: PathBuf, hint: String },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Missing { path, hint } => {
+                write!(f, "File \"{}\" does not exist. {hint}", path.display())
+            }
+            ReadError::Empty { path, hint } => {
+                write!(f, "File \"{}\" is empty. {hint}", path.display())
+            }
+            ReadError::Io { path, source } => {
+                write!(f, "Could not read \"{}\": {source}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+/// Normalize line endings to `\n` and strip a single trailing newline, so that inputs read the same
+/// regardless of how they were saved.
+pub fn normalize_input(s: &str) -> String {
+    let s = s.replace("\r\n", "\n");
+    s.strip_suffix('\n').unwrap_or(&s).to_string()
+}
+
+fn data_path(folder: &str, puzzle: PuzzleId, file_name: &str) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join(puzzle.year.to_string())
+        .join(folder)
+        .join(file_name)
+}
+
+fn read_path(path: PathBuf, hint: impl Fn() -> String) -> Result<String, ReadError> {
+    match fs::read_to_string(&path) {
+        Ok(s) if s.trim().is_empty() => Err(ReadError::Empty { path, hint: hint() }),
+        Ok(s) => Ok(normalize_input(&s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Err(ReadError::Missing { path, hint: hint() })
+        }
+        Err(source) => Err(ReadError::Io { path, source }),
+    }
+}
+
+fn missing_file_hint(folder: &str, puzzle: PuzzleId) -> String {
+    match folder {
+        "inputs" => format!(
+            "Run `cargo download {} --year {}` to download it.",
+            puzzle.day.into_inner(),
+            puzzle.year
+        ),
+        _ => "Copy it from the puzzle description.".into(),
+    }
+}
+
+/// Reads a text file to a string, e.g. like `data/2016/inputs/01.txt`.
+/// Missing and empty files are reported as errors.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> Result<String, ReadError> {
+    let path = data_path(folder, puzzle, &format!("{}.txt", puzzle.day));
+    read_path(path, || missing_file_hint(folder, puzzle))
+}
+
+/// Helper function that reads a text file to a string. E.g. like `data/2016/inputs/01.txt`.
+/// Panics with the path of the file if it is missing or empty.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    let path = data_path(folder, puzzle, &format!("{}-{part}.txt", puzzle.day));
+    read_path(path, || missing_file_hint(folder, puzzle)).unwrap_or_else(|e| panic!("{e}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
+///
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// An optional `parse = <fn>` parameter runs the given function once on the input and passes its
+/// result to both parts, e.g. `solution!(2016, 4, parse = parse_input)`. The parse step is timed separately.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Besides `main`, this generates a `solve_in_process` function that the aggregated binary uses to run the
+/// solution in-process, see [`in_process`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 1 $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 2 $(, parse = $parse:expr)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $year:expr, $day:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
+        /// The current puzzle.
+        pub const PUZZLE: $crate::template::PuzzleId = $crate::puzzle!($year, $day);
 
-        #[cfg(feature = "dhat-heap")]
+        #[cfg(all(feature = "dhat-heap", not(feature = "aggregate")))]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap"), not(feature = "aggregate")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::input::read_input(PUZZLE);
+            $( let input = run_parse($parse, input.as_str()); )?
+            $( run_part($func, &input, PUZZLE, $part); )*
+        }
+
+        /// Runs the solution without printing, returning a record for each step.
+        #[allow(dead_code)]
+        pub fn solve_in_process(
+            input: &str,
+            bench: Option<$crate::template::runner::BenchConfig>,
+        ) -> Vec<$crate::template::record::Record> {
+            use $crate::template::runner::*;
+            let mut records = vec![];
+            $(
+                let (input, record) = record_parse($parse, input, bench);
+                records.push(record);
+            )?
+            $( records.push(record_part($func, &input, $part, bench)); )*
+            records
         }
     };
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalize_input, read_path, ReadError};
+    use std::{env, fs};
+
+    #[test]
+    fn normalizes_inputs() {
+        assert_eq!(normalize_input("a\r\nb\r\n"), "a\nb");
+        assert_eq!(normalize_input("a\nb\n\n"), "a\nb\n");
+        assert_eq!(normalize_input("a"), "a");
+    }
+
+    #[test]
+    fn reports_missing_and_empty_files() {
+        let dir = env::temp_dir().join("advent_of_code_read_file");
+        fs::create_dir_all(&dir).unwrap();
+        let hint = || "hint".to_string();
+
+        let missing = read_path(dir.join("missing.txt"), hint).unwrap_err();
+        assert!(matches!(missing, ReadError::Missing { .. }));
+        assert!(missing
+            .to_string()
+            .contains("missing.txt\" does not exist. hint"));
+
+        fs::write(dir.join("empty.txt"), "\n").unwrap();
+        let empty = read_path(dir.join("empty.txt"), hint).unwrap_err();
+        assert!(matches!(empty, ReadError::Empty { .. }));
+
+        fs::write(dir.join("input.txt"), "1\r\n2\r\n").unwrap();
+        assert_eq!(read_path(dir.join("input.txt"), hint).unwrap(), "1\n2");
+    }
+}
@@ -0,0 +1,385 @@
+/// Tracks which parts of each day are solved, derived from solution output and the answer ledger.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::readme_progress;
+use crate::template::run_multi::child_commands::parse_answer;
+use crate::template::{Day, PuzzleId, Year};
+
+static PROGRESS_FILE_PATH: &str = "./data/progress.json";
+
+/// Status of a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returns `None`, e.g. the stub of the scaffold templates.
+    Unsolved,
+    /// The part returns an answer that has not been accepted (yet).
+    Answered,
+    /// The part returns the accepted answer.
+    Solved,
+}
+
+impl Status {
+    /// Derive the status of a part from its answer and the answer ledger.
+    pub fn derive(answers: &Answers, puzzle: PuzzleId, part: u8, answer: Option<&str>) -> Self {
+        match answer {
+            None => Status::Unsolved,
+            Some(answer) if answers.correct_answer(puzzle, part) == Some(answer) => Status::Solved,
+            Some(_) => Status::Answered,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Unsolved => "unsolved",
+            Status::Answered => "answered",
+            Status::Solved => "solved",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unsolved" => Ok(Status::Unsolved),
+            "answered" => Ok(Status::Answered),
+            "solved" => Ok(Status::Solved),
+            _ => Err(format!("Unknown status `{s}`.")),
+        }
+    }
+}
+
+/// Status of both parts of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayProgress {
+    pub puzzle: PuzzleId,
+    pub part_1: Status,
+    pub part_2: Status,
+}
+
+impl DayProgress {
+    /// Derive the progress of a day from the output of its solution.
+    pub fn from_output(answers: &Answers, puzzle: PuzzleId, output: &[String]) -> Self {
+        let status =
+            |part| Status::derive(answers, puzzle, part, parse_answer(output, part).as_deref());
+
+        Self {
+            puzzle,
+            part_1: status(1),
+            part_2: status(2),
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        [self.part_1, self.part_2]
+            .into_iter()
+            .filter(|s| *s == Status::Solved)
+            .count()
+    }
+}
+
+/// Represents the progress of every day that was run so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    pub data: Vec<DayProgress>,
+}
+
+impl Progress {
+    /// Dehydrate progress to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(PROGRESS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate progress from a JSON file. If not present, returns empty progress.
+    /// Fails if the file cannot be read or parsed.
+    pub fn load() -> Result<Self, String> {
+        match fs::read_to_string(PROGRESS_FILE_PATH) {
+            Ok(s) => Progress::try_from(s).map_err(|e| format!("{PROGRESS_FILE_PATH}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Progress::default()),
+            Err(e) => Err(format!("{PROGRESS_FILE_PATH}: {e}")),
+        }
+    }
+
+    /// Merge two sets of progress, overwriting `self` with `new` if present.
+    /// Parts that are unsolved in `new` keep their stored status, e.g. when a day timed out before finishing them.
+    pub fn merge(&self, new: &Self) -> Self {
+        let keep = |new: Status, stored: Status| match new {
+            Status::Unsolved => stored,
+            _ => new,
+        };
+
+        let mut data: Vec<DayProgress> = new
+            .data
+            .iter()
+            .map(|day| match self.get(day.puzzle) {
+                Some(stored) => DayProgress {
+                    puzzle: day.puzzle,
+                    part_1: keep(day.part_1, stored.part_1),
+                    part_2: keep(day.part_2, stored.part_2),
+                },
+                None => day.clone(),
+            })
+            .collect();
+
+        for day in &self.data {
+            if !data.iter().any(|d| d.puzzle == day.puzzle) {
+                data.push(day.clone());
+            }
+        }
+
+        data.sort_unstable_by_key(|d| d.puzzle);
+        Progress { data }
+    }
+
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&DayProgress> {
+        self.data.iter().find(|d| d.puzzle == puzzle)
+    }
+
+    /// Years with recorded progress, in ascending order.
+    pub fn years(&self) -> Vec<Year> {
+        let mut years: Vec<Year> = self.data.iter().map(|d| d.puzzle.year).collect();
+        years.sort_unstable();
+        years.dedup();
+        years
+    }
+
+    pub fn stars(&self, year: Year) -> usize {
+        self.data
+            .iter()
+            .filter(|d| d.puzzle.year == year)
+            .map(DayProgress::stars)
+            .sum()
+    }
+}
+
+/// Merge progress into the stored progress and render it into the readme.
+pub fn update(new: &Progress) {
+    if new.data.is_empty() {
+        return;
+    }
+
+    let merged = match Progress::load() {
+        Ok(stored) => stored.merge(new),
+        Err(e) => {
+            eprintln!("Failed to read progress {e}");
+            return;
+        }
+    };
+
+    if merged.store_file().is_err() || readme_progress::update(&merged).is_err() {
+        eprintln!("Failed to store updated progress.");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Progress> for JsonValue {
+    fn from(value: Progress) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Progress {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Progress {
+            data: json_data
+                .iter()
+                .map(DayProgress::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayProgress> for JsonValue {
+    fn from(value: &DayProgress) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year.to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert("part_1".into(), JsonValue::String(value.part_1.to_string()));
+        map.insert("part_2".into(), JsonValue::String(value.part_2.to_string()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayProgress {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected progress to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected progress.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected progress.day to be a Day struct.")?;
+
+        let status = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected progress.{key} to be a string."))?
+                .parse()
+        };
+
+        Ok(DayProgress {
+            puzzle: PuzzleId::new(year, day),
+            part_1: status("part_1")?,
+            part_2: status("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayProgress, Progress, Status};
+    use crate::template::answers::{Answers, Verdict};
+    use crate::template::record::{Record, Step};
+    use crate::template::timings::Stats;
+    use crate::template::{Day, PuzzleId};
+    use crate::{puzzle, year};
+    use std::time::Duration;
+
+    fn record(part: u8, answer: Option<&str>) -> String {
+        let stats = Stats::from_durations(&[Duration::from_nanos(10)]);
+        Record::new(Step::Part(part), answer.map(str::to_string), stats).to_line()
+    }
+
+    #[test]
+    fn derives_status_from_output() {
+        let mut answers = Answers::default();
+        answers.record(puzzle!(2016, 1), 1, "42", Verdict::Correct);
+
+        let output = [record(1, Some("42")), record(2, None)];
+        let progress = DayProgress::from_output(&answers, puzzle!(2016, 1), &output);
+        assert_eq!(progress.part_1, Status::Solved);
+        assert_eq!(progress.part_2, Status::Unsolved);
+        assert_eq!(progress.stars(), 1);
+
+        let output = [record(1, Some("41")), record(2, Some("7"))];
+        let progress = DayProgress::from_output(&answers, puzzle!(2016, 1), &output);
+        assert_eq!(progress.part_1, Status::Answered);
+        assert_eq!(progress.part_2, Status::Answered);
+        assert_eq!(progress.stars(), 0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let progress = Progress {
+            data: vec![DayProgress {
+                puzzle: puzzle!(2016, 3),
+                part_1: Status::Solved,
+                part_2: Status::Answered,
+            }],
+        };
+
+        let json = tinyjson::JsonValue::from(progress).stringify().unwrap();
+        let parsed = Progress::try_from(json).unwrap();
+        assert_eq!(parsed.data.len(), 1);
+        assert_eq!(parsed.data[0].part_2, Status::Answered);
+    }
+
+    #[test]
+    fn merges_progress() {
+        let day = |d, part_1| DayProgress {
+            puzzle: PuzzleId::new(year!(2016), Day::new(d).unwrap()),
+            part_1,
+            part_2: Status::Unsolved,
+        };
+        let stored = Progress {
+            data: vec![day(1, Status::Solved), day(2, Status::Answered)],
+        };
+        let new = Progress {
+            data: vec![day(2, Status::Solved)],
+        };
+
+        let merged = stored.merge(&new);
+        assert_eq!(merged.data.len(), 2);
+        assert_eq!(merged.stars(year!(2016)), 2);
+    }
+
+    #[test]
+    fn keeps_stored_stars_of_timed_out_days() {
+        let mut answers = Answers::default();
+        answers.record(puzzle!(2016, 1), 1, "42", Verdict::Correct);
+        answers.record(puzzle!(2016, 1), 2, "7", Verdict::Correct);
+
+        let stored = Progress {
+            data: vec![DayProgress {
+                puzzle: puzzle!(2016, 1),
+                part_1: Status::Solved,
+                part_2: Status::Solved,
+            }],
+        };
+
+        // killed while running part 2, so its record is missing.
+        let output = [record(1, Some("42"))];
+        let new = Progress {
+            data: vec![DayProgress::from_output(
+                &answers,
+                puzzle!(2016, 1),
+                &output,
+            )],
+        };
+        assert_eq!(new.data[0].part_2, Status::Unsolved);
+
+        let merged = stored.merge(&new);
+        assert_eq!(merged.data[0].part_2, Status::Solved);
+        assert_eq!(merged.stars(year!(2016)), 2);
+    }
+}
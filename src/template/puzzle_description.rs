@@ -0,0 +1,173 @@
+/// Extracts details used by `scaffold` from a downloaded puzzle description, e.g. `data/2016/puzzles/01.md`.
+/// Detection is best-effort: anything that cannot be found is left as `None`.
+use std::fs;
+
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::PuzzleId;
+
+const PART_TWO_HEADER: &str = "--- Part Two ---";
+const OWN_ANSWER: &str = "Your puzzle answer was";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleDescription {
+    /// The title without the day, e.g. `No Time for a Taxicab`.
+    pub title: Option<String>,
+    /// Content of the first code block of part one.
+    pub example: Option<String>,
+    /// The answer of the example of each part, if it looks like one.
+    pub part_one_answer: Option<String>,
+    pub part_two_answer: Option<String>,
+}
+
+impl PuzzleDescription {
+    /// Read the description of a puzzle, if it was downloaded.
+    pub fn read(puzzle: PuzzleId) -> Option<Self> {
+        let markdown = fs::read_to_string(get_puzzle_path(puzzle)).ok()?;
+        Some(Self::parse(&markdown))
+    }
+
+    pub fn parse(markdown: &str) -> Self {
+        let (part_one, part_two) = match find_part_two(markdown) {
+            Some(index) => (&markdown[..index], Some(&markdown[index..])),
+            None => (markdown, None),
+        };
+
+        Self {
+            title: parse_title(part_one),
+            example: code_blocks(part_one).into_iter().next(),
+            part_one_answer: parse_answer(part_one),
+            part_two_answer: part_two.and_then(parse_answer),
+        }
+    }
+}
+
+/// Byte offset of the part two header, which may be escaped like the title.
+fn find_part_two(markdown: &str) -> Option<usize> {
+    let mut offset = 0;
+
+    for line in markdown.split_inclusive('\n') {
+        if line.starts_with('#') && line.replace('\\', "").contains(PART_TWO_HEADER) {
+            return Some(offset);
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Parse a header like `## --- Day 1: No Time for a Taxicab ---`.
+/// Dashes may be escaped by other markdown converters, e.g. `\-\-\-`.
+fn parse_title(markdown: &str) -> Option<String> {
+    let header = markdown
+        .lines()
+        .find(|l| l.starts_with('#'))?
+        .replace('\\', "");
+    let (_, title) = header.split_once(':')?;
+    let title = title.trim().trim_end_matches('-').trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Contents of the fenced code blocks, in order.
+fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some(String::new()),
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+
+    blocks
+}
+
+/// Puzzles usually state the example's answer in the last inline code span of the description,
+/// e.g. "... is `12` blocks away". Only short values without spaces are considered answers.
+/// Once a part is solved, the description also states the user's own answer, which is skipped.
+fn parse_answer(markdown: &str) -> Option<String> {
+    let mut in_block = false;
+    let mut answer = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+        if in_block || line.contains(OWN_ANSWER) {
+            continue;
+        }
+
+        for (index, span) in line.split('`').enumerate() {
+            // odd segments are inside backticks.
+            let value = span.trim_matches('*');
+            if index % 2 == 1 && !value.is_empty() && value.len() <= 20 && !value.contains(' ') {
+                answer = Some(value.to_string());
+            }
+        }
+    }
+
+    answer
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleDescription;
+
+    const DESCRIPTION: &str = "## --- Day 1: No Time for a Taxicab ---
+
+Following `R2, L3` leaves you *2 blocks East*:
+
+```
+R5, L5,
+R5, R3
+```
+
+For example, `R5, L5, R5, R3` leaves you `12` blocks away.
+
+How many blocks away is Easter Bunny HQ?
+
+## --- Part Two ---
+
+For example, if your instructions are `R8, R4, R4, R8`, the first location you visit twice is `4` blocks away.";
+
+    #[test]
+    fn parses_description() {
+        let description = PuzzleDescription::parse(DESCRIPTION);
+        assert_eq!(description.title.as_deref(), Some("No Time for a Taxicab"));
+        assert_eq!(description.example.as_deref(), Some("R5, L5,\nR5, R3\n"));
+        assert_eq!(description.part_one_answer.as_deref(), Some("12"));
+        assert_eq!(description.part_two_answer.as_deref(), Some("4"));
+    }
+
+    #[test]
+    fn handles_escaped_headers() {
+        let description = PuzzleDescription::parse(
+            "## \\-\\-\\- Day 4: Security Through Obscurity \\-\\-\\-\n\nNo example.\n\n\
+             ## \\-\\-\\- Part Two \\-\\-\\-\n\nThe answer is `42`.",
+        );
+        assert_eq!(
+            description.title.as_deref(),
+            Some("Security Through Obscurity")
+        );
+        assert_eq!(description.example, None);
+        assert_eq!(description.part_one_answer, None);
+        assert_eq!(description.part_two_answer.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn skips_own_answers() {
+        let description = PuzzleDescription::parse(&format!(
+            "{DESCRIPTION}\n\nWhat is the answer?\n\nYour puzzle answer was `161`.\n"
+        ));
+        assert_eq!(description.part_one_answer.as_deref(), Some("12"));
+        assert_eq!(description.part_two_answer.as_deref(), Some("4"));
+    }
+}
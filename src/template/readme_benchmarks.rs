@@ -1,11 +1,15 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{fs, io, path::Path};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::timings::{format_bytes, Stats, Timing, Timings};
+use crate::template::PuzzleId;
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static CHART_PATH: &str = "./.assets/benchmarks.svg";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -21,17 +25,18 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Find the section of the readme enclosed by two occurences of `marker`.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,78 +57,322 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Names of the optional parts of the benchmark table, as passed to `cargo time --readme-extras`.
+pub const EXTRAS: [&str; 6] = ["parse", "samples", "spread", "delta", "memory", "chart"];
+
+/// Optional parts of the benchmark table, all disabled by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Show a column for the parse step.
+    pub parse: bool,
+    /// Show the fewest samples taken of any step.
+    pub samples: bool,
+    /// Show the standard deviation next to the median.
+    pub spread: bool,
+    /// Show the change of the total time compared to the previous run.
+    pub delta: bool,
+    /// Link a bar chart of the total time of each day.
+    pub chart: bool,
+    /// Show the allocations and peak heap usage measured with `cargo time --memory`.
+    pub memory: bool,
+}
+
+impl FromStr for TableOptions {
+    type Err = String;
+
+    /// Parse a comma-separated list of [`EXTRAS`], e.g. `parse,chart`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = TableOptions::default();
+
+        for name in s.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            let enabled = match name {
+                "parse" => &mut options.parse,
+                "samples" => &mut options.samples,
+                "spread" => &mut options.spread,
+                "delta" => &mut options.delta,
+                "memory" => &mut options.memory,
+                "chart" => &mut options.chart,
+                _ => {
+                    return Err(format!(
+                        "unknown readme extra `{name}`, expecting some of {}.",
+                        EXTRAS.join(", ")
+                    ))
+                }
+            };
+            *enabled = true;
+        }
+
+        Ok(options)
+    }
+}
+
+fn all_stats(timing: &Timing) -> impl Iterator<Item = &Stats> {
+    [
+        &timing.parse_stats,
+        &timing.part_1_stats,
+        &timing.part_2_stats,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Why a part has no time, e.g. `failed`, or `-` if it's not solved yet.
+fn missing_reason(timing: &Timing, part: u8) -> &'static str {
+    if timing.failed.contains(&part) {
+        "failed"
+    } else if timing.timed_out {
+        "timed out"
+    } else {
+        "-"
+    }
+}
+
+fn format_cell(
+    median: Option<&String>,
+    stats: Option<&Stats>,
+    spread: bool,
+    missing: &str,
+) -> String {
+    match (median, stats) {
+        (None, _) => format!("`{missing}`"),
+        (Some(median), Some(stats)) if spread => format!("`{median} ± {:.1?}`", stats.std_dev()),
+        (Some(median), _) => format!("`{median}`"),
+    }
+}
+
+fn format_delta(timings: &Timings, timing: &Timing) -> String {
+    match timings.previous(timing.puzzle) {
+        Some(previous) if previous.total_nanos > 0.0 => {
+            let change = timing.total_nanos / previous.total_nanos - 1.0;
+            format!("`{:+.1}%`", change * 100.0)
+        }
+        _ => "`-`".into(),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: &Timings,
+    total_millis: f64,
+    options: TableOptions,
+) -> String {
     let header = format!("{prefix} Benchmarks");
+    // the year is only needed to tell days apart once several years are benched.
+    let show_year = timings
+        .data
+        .iter()
+        .any(|t| t.puzzle.year != timings.data[0].puzzle.year);
+
+    let mut columns = vec![];
+    if show_year {
+        columns.push("Year");
+    }
+    columns.push("Day");
+    if options.parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if options.samples {
+        columns.push("Samples");
+    }
+    if options.delta {
+        columns.push("Δ");
+    }
+    if options.memory {
+        columns.extend(["Allocations", "Peak heap"]);
+    }
 
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", columns.join(" | ")),
+        format!("|{} |", " :---: |".repeat(columns.len() - 1) + " :---: "),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+    for timing in &timings.data {
+        let path = get_path_for_bin(timing.puzzle);
+
+        let mut cells = vec![];
+        if show_year {
+            cells.push(timing.puzzle.year.to_string());
+        }
+        cells.push(format!(
+            "[Day {}]({})",
+            timing.puzzle.day.into_inner(),
+            path
+        ));
+        if options.parse {
+            cells.push(format_cell(
+                timing.parse.as_ref(),
+                timing.parse_stats.as_ref(),
+                options.spread,
+                "-",
+            ));
+        }
+        cells.push(format_cell(
+            timing.part_1.as_ref(),
+            timing.part_1_stats.as_ref(),
+            options.spread,
+            missing_reason(timing, 1),
+        ));
+        cells.push(format_cell(
+            timing.part_2.as_ref(),
+            timing.part_2_stats.as_ref(),
+            options.spread,
+            missing_reason(timing, 2),
         ));
+        if options.samples {
+            let samples = all_stats(timing).map(|s| s.samples).min();
+            cells.push(samples.map_or_else(|| "-".into(), |s| s.to_string()));
+        }
+        if options.delta {
+            cells.push(format_delta(timings, timing));
+        }
+        if options.memory {
+            match timing.memory() {
+                Some(memory) => cells.extend([
+                    format!("`{}`", memory.allocations),
+                    format!("`{}`", format_bytes(memory.peak_bytes)),
+                ]),
+                None => cells.extend(["`-`".into(), "`-`".into()]),
+            }
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+    if options.chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark chart]({CHART_PATH})"));
+    }
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Render a horizontal bar chart of the total time of each day.
+/// Times span several orders of magnitude, so bars use a logarithmic scale.
+fn construct_chart(timings: &Timings) -> String {
+    const LABEL_WIDTH: f64 = 70.0;
+    const BAR_WIDTH: f64 = 400.0;
+    const ROW_HEIGHT: f64 = 22.0;
+
+    let days: Vec<&Timing> = timings
+        .data
+        .iter()
+        .filter(|t| t.total_nanos > 0.0)
+        .collect();
+
+    // start the scale an order of magnitude below the fastest day, so that it still gets a bar.
+    let min = days
+        .iter()
+        .map(|t| t.total_nanos.log10())
+        .fold(f64::INFINITY, f64::min)
+        .floor()
+        - 1.0;
+    let max = days
+        .iter()
+        .map(|t| t.total_nanos.log10())
+        .fold(f64::NEG_INFINITY, f64::max)
+        .max(min + 1.0);
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = ROW_HEIGHT * days.len() as f64 + 10.0;
+    let width = LABEL_WIDTH + BAR_WIDTH + 90.0;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"sans-serif\" font-size=\"12\">\n"
+    );
+
+    for (row, timing) in (0_u32..).zip(&days) {
+        let y = f64::from(row) * ROW_HEIGHT + 5.0;
+        let bar = (timing.total_nanos.log10() - min) / (max - min) * BAR_WIDTH;
+        let label = format!("{}/{}", timing.puzzle.year, timing.puzzle.day.into_inner());
+        let duration = format!("{:.1?}", Duration::from_secs_f64(timing.total_nanos / 1e9));
+
+        let _ = writeln!(svg, "  <text x=\"0\" y=\"{}\">{label}</text>", y + 14.0);
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{LABEL_WIDTH}\" y=\"{y}\" width=\"{bar:.1}\" height=\"{}\" fill=\"#b8860b\" />",
+            ROW_HEIGHT - 4.0
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{:.1}\" y=\"{}\">{duration}</text>",
+            LABEL_WIDTH + bar + 6.0,
+            y + 14.0
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn update_content(
+    s: &mut String,
+    timings: &Timings,
+    total_millis: f64,
+    options: TableOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(timings: Timings, options: TableOptions) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, &timings, total_millis, options)?;
     fs::write(path, &readme)?;
+
+    if options.chart && !timings.data.is_empty() {
+        if let Some(dir) = Path::new(CHART_PATH).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(CHART_PATH, construct_chart(&timings))?;
+    }
+
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{construct_chart, construct_table, update_content, TableOptions, MARKER};
+    use crate::{
+        puzzle,
+        template::timings::{Memory, Stats, Timing, Timings},
+    };
+    use std::time::Duration;
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(puzzle!(2016, 1))
                 },
                 Timing {
-                    day: day!(2),
+                    parse: Some("5ms".into()),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(puzzle!(2016, 2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Timing::new(puzzle!(2016, 4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -131,28 +380,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0, TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0, TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0, TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0, TableOptions::default()).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0, TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +409,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), 190.0, TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +418,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2016_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2016_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2016_04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +429,121 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_extras() {
+        assert_eq!("".parse(), Ok(TableOptions::default()));
+        assert_eq!(
+            "parse, chart".parse(),
+            Ok(TableOptions {
+                parse: true,
+                chart: true,
+                ..TableOptions::default()
+            })
+        );
+        assert!("parse,stars".parse::<TableOptions>().is_err());
+    }
+
+    #[test]
+    fn formats_parse_column_and_chart() {
+        let options = "parse,chart".parse().unwrap();
+        let table = construct_table("##", &get_mock_timings(), 190.0, options);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[3], "| Day | Parse | Part 1 | Part 2 |");
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/2016_02.rs) | `5ms` | `30ms` | `40ms` |"
+        );
+        assert_eq!(
+            lines[lines.len() - 2],
+            "![Benchmark chart](./.assets/benchmarks.svg)"
+        );
+    }
+
+    #[test]
+    fn shows_year_for_several_years() {
+        let mut timings = get_mock_timings();
+        timings.data[2].puzzle = puzzle!(2017, 4);
+
+        let table = construct_table("##", &timings, 190.0, TableOptions::default());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[3], "| Year | Day | Part 1 | Part 2 |");
+        assert_eq!(
+            lines[7],
+            "| 2017 | [Day 4](./src/bin/2017_04.rs) | `40ms` | `50ms` |"
+        );
+    }
+
+    #[test]
+    fn formats_optional_columns() {
+        let mut timings = get_mock_timings();
+        timings.data.iter_mut().for_each(|t| t.parse = None);
+
+        let durations: Vec<Duration> = (1..=4).map(Duration::from_millis).collect();
+        timings.data[0].part_1_stats = Some(Stats::from_durations(&durations));
+        timings.history.push(Timing {
+            total_nanos: 6e+10,
+            ..timings.data[0].clone()
+        });
+
+        let options = "samples,spread,delta".parse().unwrap();
+        let table = construct_table("##", &timings, 190.0, options);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[3], "| Day | Part 1 | Part 2 | Samples | Δ |");
+        assert_eq!(lines[4], "| :---: | :---: | :---: | :---: | :---:  |");
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/2016_01.rs) | `10ms ± 1.1ms` | `20ms` | 4 | `-50.0%` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/2016_02.rs) | `30ms` | `40ms` | - | `-` |"
+        );
+    }
+
+    #[test]
+    fn marks_failed_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].failed = vec![2];
+
+        let table = construct_table("##", &timings, 190.0, TableOptions::default());
+        assert!(table.contains("| [Day 4](./src/bin/2016_04.rs) | `40ms` | `failed` |"));
+    }
+
+    #[test]
+    fn marks_timed_out_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].timed_out = true;
+
+        let table = construct_table("##", &timings, 190.0, TableOptions::default());
+        assert!(table.contains("| [Day 4](./src/bin/2016_04.rs) | `40ms` | `timed out` |"));
+    }
+
+    #[test]
+    fn formats_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(Memory {
+            allocations: 12,
+            total_bytes: 4096,
+            peak_bytes: 2048,
+        });
+
+        let options = "memory".parse().unwrap();
+        let table = construct_table("##", &timings, 190.0, options);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[3].ends_with("| Part 2 | Allocations | Peak heap |"));
+        assert!(lines[5].ends_with("| `20ms` | `12` | `2.0 KiB` |"));
+        assert!(lines[6].ends_with("| `40ms` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn draws_chart() {
+        let chart = construct_chart(&get_mock_timings());
+        assert!(chart.starts_with("<svg"));
+        assert_eq!(chart.matches("<rect").count(), 3);
+        assert!(chart.contains(">2016/4</text>"));
+        assert!(chart.contains(">90.0s</text>"));
+    }
 }
@@ -0,0 +1,136 @@
+/// Module that updates the readme with an advent calendar of solved stars.
+/// Works like [`super::readme_benchmarks`], but uses its own marker.
+use std::fs;
+
+use crate::template::progress::{Progress, Status};
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::{all_days, PuzzleId, Year};
+
+static MARKER: &str = "<!--- progress grid --->";
+
+/// Days are laid out like an advent calendar, five per row.
+const COLUMNS: usize = 5;
+
+fn format_status(status: Status) -> &'static str {
+    match status {
+        Status::Solved => "⭐",
+        Status::Answered => "☆",
+        Status::Unsolved => "",
+    }
+}
+
+fn construct_grid(year: Year, progress: &Progress) -> Vec<String> {
+    let mut lines = vec![
+        format!("### {year} ({}/50 ⭐)", progress.stars(year)),
+        String::new(),
+        format!("|{}", "     |".repeat(COLUMNS)),
+        format!("|{}", " :---: |".repeat(COLUMNS)),
+    ];
+
+    let cells: Vec<String> = all_days()
+        .map(|day| {
+            let stars = progress
+                .get(PuzzleId::new(year, day))
+                .map_or(String::new(), |p| {
+                    format!("{}{}", format_status(p.part_1), format_status(p.part_2))
+                });
+            format!("**{}**<br>{stars}", day.into_inner())
+        })
+        .collect();
+
+    for row in cells.chunks(COLUMNS) {
+        lines.push(format!("| {} |", row.join(" | ")));
+    }
+
+    lines
+}
+
+fn construct_table(prefix: &str, progress: &Progress) -> String {
+    let mut lines: Vec<String> = vec![MARKER.into(), format!("{prefix} Progress")];
+
+    for year in progress.years() {
+        lines.push(String::new());
+        lines.extend(construct_grid(year, progress));
+    }
+
+    lines.push(String::new());
+    lines.push("⭐ accepted answer, ☆ answer not accepted yet".into());
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(progress: &Progress) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::puzzle;
+    use crate::template::progress::{DayProgress, Progress, Status};
+
+    fn get_mock_progress() -> Progress {
+        Progress {
+            data: vec![
+                DayProgress {
+                    puzzle: puzzle!(2016, 1),
+                    part_1: Status::Solved,
+                    part_2: Status::Solved,
+                },
+                DayProgress {
+                    puzzle: puzzle!(2016, 7),
+                    part_1: Status::Solved,
+                    part_2: Status::Answered,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress()).unwrap();
+    }
+
+    #[test]
+    fn updates_existing_grid() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## Progress").count(), 1);
+    }
+
+    #[test]
+    fn formats_grid() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+
+        assert_eq!(lines[3], "### 2016 (3/50 ⭐)");
+        assert_eq!(lines[5], "|     |     |     |     |     |");
+        assert_eq!(lines[6], "| :---: | :---: | :---: | :---: | :---: |");
+        assert_eq!(
+            lines[7],
+            "| **1**<br>⭐⭐ | **2**<br> | **3**<br> | **4**<br> | **5**<br> |"
+        );
+        assert_eq!(
+            lines[8],
+            "| **6**<br> | **7**<br>⭐☆ | **8**<br> | **9**<br> | **10**<br> |"
+        );
+        assert_eq!(lines.len(), 15);
+    }
+}
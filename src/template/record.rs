@@ -0,0 +1,222 @@
+/// Machine-readable output of solution binaries, emitted with the `--json` flag.
+/// Each step of a solution (the optional parse step and each part) prints one JSON object per line.
+use std::{collections::HashMap, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{parse_optional_memory, Memory, Stats};
+use crate::template::PuzzleId;
+
+/// The step of a solution a record belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Step {
+    fn as_str(self) -> &'static str {
+        match self {
+            Step::Parse => "parse",
+            Step::Part(1) => "part_1",
+            Step::Part(_) => "part_2",
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Step::Parse),
+            "part_1" => Ok(Step::Part(1)),
+            "part_2" => Ok(Step::Part(2)),
+            _ => Err(format!("Unknown step `{s}`.")),
+        }
+    }
+}
+
+/// Result and timing of a single solution step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// Only set when several puzzles share an output, i.e. when running in-process.
+    pub puzzle: Option<PuzzleId>,
+    pub step: Step,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    /// The formatted median duration, e.g. `1.2ms`.
+    pub duration: String,
+    pub stats: Stats,
+    /// Heap usage, only measured by builds with the `dhat-heap` or `count-allocs` feature.
+    pub memory: Option<Memory>,
+}
+
+impl Record {
+    pub fn new(step: Step, answer: Option<String>, stats: Stats) -> Self {
+        Self {
+            puzzle: None,
+            step,
+            answer,
+            error: None,
+            duration: format!("{:.1?}", stats.median()),
+            stats,
+            memory: None,
+        }
+    }
+
+    /// Serialize the record to a single line of JSON.
+    pub fn to_line(&self) -> String {
+        // NOTE: stringify only fails for NaN / infinite numbers, which stats never contain.
+        JsonValue::from(self).stringify().unwrap()
+    }
+
+    /// Parse a line of solution output. Returns `None` for lines that are not records,
+    /// e.g. debug output printed by a solution.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        Record::try_from(&json).ok()
+    }
+}
+
+/// Collect the records contained in the output of a solution.
+pub fn parse_records(output: &[String]) -> Vec<Record> {
+    output.iter().filter_map(|l| Record::from_line(l)).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |s| JsonValue::String(s.clone()))
+}
+
+impl From<&Record> for JsonValue {
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let puzzle = value.puzzle.map(|p| p.to_string());
+        map.insert("puzzle".into(), optional_string(puzzle.as_ref()));
+        map.insert("step".into(), JsonValue::String(value.step.as_str().into()));
+        map.insert("answer".into(), optional_string(value.answer.as_ref()));
+        map.insert("error".into(), optional_string(value.error.as_ref()));
+        map.insert("duration".into(), JsonValue::String(value.duration.clone()));
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let step = json
+            .get("step")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.step to be a string.")?
+            .parse()?;
+
+        let optional = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected record.{key} to be null or string.")),
+        };
+
+        let duration = json
+            .get("duration")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.duration to be a string.")?;
+
+        let stats = Stats::try_from(json.get("stats").ok_or("Expected record.stats.")?)?;
+
+        let puzzle = optional("puzzle")?
+            .map(|p| p.parse())
+            .transpose()
+            .map_err(|e| format!("Expected record.puzzle to be a puzzle id: {e}"))?;
+
+        Ok(Record {
+            puzzle,
+            step,
+            answer: optional("answer")?,
+            error: optional("error")?,
+            duration: duration.clone(),
+            stats,
+            memory: parse_optional_memory(json.get("memory"))?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_records, Record, Step};
+    use crate::{
+        puzzle,
+        template::timings::{Memory, Stats},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_records() {
+        let stats = Stats::from_durations(&[Duration::from_micros(1200)]);
+        let record = Record::new(Step::Part(2), Some("a (1 samples)\nb".into()), stats);
+
+        let line = record.to_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(Record::from_line(&line), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_memory() {
+        let stats = Stats::from_durations(&[Duration::from_micros(3)]);
+        let mut record = Record::new(Step::Part(1), Some("1".into()), stats);
+        record.memory = Some(Memory {
+            allocations: 12,
+            total_bytes: 4096,
+            peak_bytes: 1024,
+        });
+
+        assert_eq!(Record::from_line(&record.to_line()), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_puzzle_ids() {
+        let stats = Stats::from_durations(&[Duration::from_micros(3)]);
+        let mut record = Record::new(Step::Parse, None, stats);
+        record.puzzle = Some(puzzle!(2016, 4));
+
+        let parsed = Record::from_line(&record.to_line()).unwrap();
+        assert_eq!(parsed.puzzle, Some(puzzle!(2016, 4)));
+    }
+
+    #[test]
+    fn skips_non_record_lines() {
+        let stats = Stats::from_durations(&[Duration::from_nanos(74)]);
+        let output = [
+            "debug output".to_string(),
+            Record::new(Step::Parse, None, stats.clone()).to_line(),
+            "{ \"not\": \"a record\" }".to_string(),
+            Record::new(Step::Part(1), None, stats).to_line(),
+        ];
+
+        let records = parse_records(&output);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].step, Step::Parse);
+        assert_eq!(records[1].step, Step::Part(1));
+        assert_eq!(records[1].duration, "74.0ns");
+    }
+}
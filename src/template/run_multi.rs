@@ -1,49 +1,194 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    runner::BenchConfig, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
+    answers::Answers,
+    progress::{DayProgress, Progress},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Options shared by the commands that run several days.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    /// Bench each part, used by `cargo time`.
+    pub bench: Option<BenchConfig>,
+    /// Run all days in a single process through the aggregated binary instead of one `cargo run` per day.
+    pub in_process: bool,
+    /// Number of days to run concurrently. Ignored when benching, to avoid noisy measurements.
+    pub jobs: Option<usize>,
+    /// Kill a day that runs longer than this and continue with the next one.
+    /// Not supported in-process, as a solution running on a thread can't be stopped.
+    pub timeout: Option<Duration>,
+}
+
+impl RunOptions {
+    fn jobs(&self) -> usize {
+        match self.bench {
+            Some(_) => 1,
+            None => self.jobs.unwrap_or(1).max(1),
+        }
+    }
+}
+
+/// Results of running several days.
+pub struct RunResults {
+    /// Only present when benching.
+    pub timings: Option<Timings>,
+    /// Progress of the days that were run.
+    pub progress: Progress,
+}
+
+pub fn run_multi(year: Year, days_to_run: &HashSet<Day>, options: RunOptions) -> RunResults {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut progress = Progress::default();
+    let answers = Answers::read_from_file();
 
     let mut need_space = false;
+    let mut print_header = |puzzle: PuzzleId| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+        println!("------");
+    };
+
+    let mut report = |puzzle: PuzzleId, output: &[String], timed_out: bool| {
+        if timed_out {
+            let timeout = options.timeout.unwrap_or_default();
+            println!("{ANSI_BOLD}Timed out{ANSI_RESET} after {timeout:.1?}.");
+        }
+
+        if output.is_empty() && !timed_out {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(output, puzzle);
+            val.timed_out = timed_out;
+            timings.push(val);
+            progress
+                .data
+                .push(DayProgress::from_output(&answers, puzzle, output));
+        }
+    };
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<PuzzleId> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        .map(|day| PuzzleId::new(year, day))
+        .collect();
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // build first, so that compile times don't count towards the timeout of the first day.
+    if options.timeout.is_some() && !options.in_process {
+        child_commands::build_solutions(options.bench, options.is_release).unwrap();
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    if options.in_process {
+        let mut outputs = child_commands::run_in_process(&puzzles, options).unwrap();
+        for puzzle in puzzles {
+            print_header(puzzle);
+            let output = outputs.remove(&puzzle).unwrap_or_default();
+            output
+                .iter()
+                .for_each(|line| child_commands::print_line(line));
+            report(puzzle, &output, false);
+        }
+    } else if options.jobs() > 1 {
+        // output is buffered per day and printed once all previous days are done.
+        run_ordered(
+            &puzzles,
+            options.jobs(),
+            |puzzle| {
+                child_commands::capture_solution(*puzzle, options.is_release, options.timeout)
+                    .unwrap()
+            },
+            |puzzle, output| {
+                print_header(*puzzle);
+                output.print();
+                report(*puzzle, &output.stdout, output.timed_out);
+            },
+        );
+    } else {
+        for puzzle in puzzles {
+            print_header(puzzle);
+            let output = child_commands::run_solution(
+                puzzle,
+                options.bench,
+                options.is_release,
+                options.timeout,
+            )
+            .unwrap();
+            report(puzzle, &output.stdout, output.timed_out);
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = options.bench.map(|_| {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
-    }
+        timings
+    });
+
+    RunResults { timings, progress }
+}
+
+/// Run `work` for each item on up to `jobs` threads, passing the results to `emit` in the order of `items`.
+/// Results of items that finish early are held back until all previous items are emitted.
+pub fn run_ordered<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(&T, R),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if sender.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_emit = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_to_emit) {
+                emit(&items[next_to_emit], result);
+                next_to_emit += 1;
+            }
+        }
+    });
 }
 
 #[allow(dead_code)]
@@ -60,133 +205,364 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_path_for_bin, Error, RunOptions};
+    use crate::template::{
+        record::{parse_records, Record, Step},
+        runner::{print_record, BenchConfig, SolutionArgs},
+        PuzzleId,
+    };
     use std::{
-        io::{BufRead, BufReader},
+        collections::HashMap,
+        io::{self, BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Output of a solution bin. Output that was forwarded while running is not captured.
+    #[derive(Default)]
+    pub struct CapturedOutput {
+        pub stdout: Vec<String>,
+        pub stderr: String,
+        /// Whether the bin was killed because it exceeded its timeout.
+        pub timed_out: bool,
+    }
+
+    impl CapturedOutput {
+        /// Print the captured output like [`run_solution`] would have.
+        pub fn print(&self) {
+            eprint!("{}", self.stderr);
+            self.stdout.iter().for_each(|line| print_line(line));
+        }
+    }
+
+    /// Print a line of solution output, formatting records like a direct `cargo solve`.
+    pub fn print_line(line: &str) {
+        match Record::from_line(line) {
+            Some(record) => print_record(&record),
+            None => println!("{line}"),
+        }
+    }
+
+    /// Cargo flags of the build that runs the solutions.
+    fn build_flags(bench: Option<BenchConfig>, is_release: bool) -> Vec<&'static str> {
+        let mut flags = vec![];
+
+        if is_release {
+            flags.push("--release");
+        }
+
+        // counting allocations is cheap enough to report memory use with every bench.
+        if bench.is_some() {
+            flags.extend(["--features", "count-allocs"]);
+        }
+
+        flags
+    }
+
+    fn solution_command(puzzle: PuzzleId, bench: Option<BenchConfig>, is_release: bool) -> Command {
+        let bin_name = puzzle.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+        args.extend(build_flags(bench, is_release));
+        args.push("--");
+
+        // ask for machine-readable records instead of the formatted output.
+        let solution_args = SolutionArgs {
+            json: true,
+            bench,
+            ..SolutionArgs::default()
+        };
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args).args(solution_args.to_args());
+        cmd
+    }
+
+    /// Build all solution bins, so that a timeout of [`run_solution`] does not include compile times.
+    /// Build errors are ignored here, they are reported when running the affected day.
+    pub fn build_solutions(bench: Option<BenchConfig>, is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+        args.extend(build_flags(bench, is_release));
+
+        Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+
+        Ok(())
+    }
+
+    /// Wait for a child to exit. Kills it and returns `true` once `timeout` has passed.
+    fn wait_with_timeout(child: &mut Child, timeout: Option<Duration>) -> io::Result<bool> {
+        let Some(timeout) = timeout else {
+            child.wait()?;
+            return Ok(false);
+        };
+
+        let deadline = Instant::now() + timeout;
+        while child.try_wait()?.is_none() {
+            if Instant::now() >= deadline {
+                // `cargo run` replaces itself with the bin on unix, so this kills the solution.
+                child.kill()?;
+                child.wait()?;
+                return Ok(true);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        Ok(false)
+    }
+
+    /// Spawn a solution command with piped output, either forwarding or capturing its output.
+    fn spawn_and_wait(
+        mut cmd: Command,
+        timeout: Option<Duration>,
+        forward: bool,
+    ) -> Result<CapturedOutput, Error> {
+        let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+        let stdout = BufReader::new(child.stdout.take().ok_or(super::Error::BrokenPipe)?);
+        let stderr = BufReader::new(child.stderr.take().ok_or(super::Error::BrokenPipe)?);
+
+        // output is read on separate threads, so that the child can be killed while it's silent.
+        let stdout_thread = thread::spawn(move || {
+            let mut lines = vec![];
+            for line in stdout.lines().map_while(Result::ok) {
+                if forward {
+                    print_line(&line);
+                }
+                lines.push(line);
+            }
+            lines
+        });
+
+        let stderr_thread = thread::spawn(move || {
+            let mut captured = String::new();
+            for line in stderr.lines().map_while(Result::ok) {
+                if forward {
+                    eprintln!("{line}");
+                } else {
+                    captured.push_str(&line);
+                    captured.push('\n');
+                }
+            }
+            captured
+        });
+
+        let timed_out = wait_with_timeout(&mut child, timeout)?;
+
+        Ok(CapturedOutput {
+            stdout: stdout_thread.join().unwrap(),
+            stderr: stderr_thread.join().unwrap(),
+            timed_out,
+        })
+    }
+
+    /// Run the solution bin for a given day, forwarding its output while grabbing stdout lines.
+    /// Records are printed in the same format as a direct `cargo solve`.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        bench: Option<BenchConfig>,
+        is_release: bool,
+        timeout: Option<Duration>,
+    ) -> Result<CapturedOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            return Ok(CapturedOutput::default());
+        }
+
+        spawn_and_wait(solution_command(puzzle, bench, is_release), timeout, true)
+    }
+
+    /// Run the solution bin for a given day with heap profiling, returning its records.
+    /// This uses a separate build, as the profiling allocator would skew timings.
+    pub fn measure_memory(puzzle: PuzzleId) -> Result<Vec<Record>, Error> {
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.to_string();
+        let solution_args = SolutionArgs {
+            json: true,
+            memory: true,
+            ..SolutionArgs::default()
+        };
 
-        if is_release {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--bin",
+                &bin_name,
+                "--profile",
+                "dhat",
+                "--features",
+                "dhat-heap",
+                "--",
+            ])
+            .args(solution_args.to_args())
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        let lines: Vec<String> = output.stdout.lines().collect::<Result<_, _>>()?;
+        Ok(parse_records(&lines))
+    }
+
+    /// Run the solution bin for a given day without printing anything, e.g. to run several days at once.
+    pub fn capture_solution(
+        puzzle: PuzzleId,
+        is_release: bool,
+        timeout: Option<Duration>,
+    ) -> Result<CapturedOutput, Error> {
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            return Ok(CapturedOutput::default());
+        }
+
+        spawn_and_wait(solution_command(puzzle, None, is_release), timeout, false)
+    }
+
+    /// Run the given puzzles with the aggregated binary, grouping its output by puzzle.
+    /// Lines that are not records, e.g. debug output, belong to the next record printed.
+    pub fn run_in_process(
+        puzzles: &[PuzzleId],
+        options: RunOptions,
+    ) -> Result<HashMap<PuzzleId, Vec<String>>, Error> {
+        let Some(first) = puzzles.first() else {
+            return Ok(HashMap::new());
+        };
+
+        let year = first.year.to_string();
+        let days = puzzles
+            .iter()
+            .map(|p| p.day.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let features = if options.bench.is_some() {
+            "aggregate,count-allocs"
+        } else {
+            "aggregate"
+        };
+
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--features",
+            features,
+            "--bin",
+            "aggregate",
+        ];
+
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        args.extend(["--", "--year", &year, "--days", &days]);
+
+        let jobs = options.jobs.map(|jobs| jobs.to_string());
+        if let Some(jobs) = &jobs {
+            args.extend(["--jobs", jobs]);
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let bench_args = options
+            .bench
+            .map(|config| config.to_args())
+            .unwrap_or_default();
+
+        if options.bench.is_some() {
+            args.push("--time");
+            args.extend(bench_args.iter().map(String::as_str));
+        }
 
+        // stderr is inherited, compiler progress and panics are shown as they happen.
         let mut cmd = Command::new("cargo")
             .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
             .spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let mut output: HashMap<PuzzleId, Vec<String>> = HashMap::new();
+        let mut pending = vec![];
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            let line = line?;
+            match Record::from_line(&line).and_then(|r| r.puzzle) {
+                Some(puzzle) => {
+                    let lines = output.entry(puzzle).or_default();
+                    lines.append(&mut pending);
+                    lines.push(line);
+                }
+                None => pending.push(line),
+            }
         }
 
-        thread.join().unwrap();
         cmd.wait()?;
 
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    pub fn parse_exec_time(output: &[String], puzzle: PuzzleId) -> super::Timing {
+        let mut timings = super::Timing::new(puzzle);
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        let records = parse_records(output);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+        timings.failed = records
+            .iter()
+            .filter(|r| r.error.is_some())
+            .filter_map(|r| match r.step {
+                Step::Part(part) => Some(part),
+                Step::Parse => None,
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            .collect();
+
+        records
+            .into_iter()
+            .filter(|r| r.step == Step::Parse || r.answer.is_some())
+            .for_each(|record| {
+                timings.total_nanos += record.stats.median_nanos;
+
+                let (timing, stats, memory) = match record.step {
+                    Step::Parse => (
+                        &mut timings.parse,
+                        &mut timings.parse_stats,
+                        &mut timings.parse_memory,
+                    ),
+                    Step::Part(1) => (
+                        &mut timings.part_1,
+                        &mut timings.part_1_stats,
+                        &mut timings.part_1_memory,
+                    ),
+                    Step::Part(_) => (
+                        &mut timings.part_2,
+                        &mut timings.part_2_stats,
+                        &mut timings.part_2_memory,
+                    ),
+                };
 
-                timings.total_nanos += nanos;
+                *timing = Some(record.duration);
+                *stats = Some(record.stats);
+                *memory = record.memory;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Extract the answer printed for a part, if it was solved.
+    pub fn parse_answer(output: &[String], part: u8) -> Option<String> {
+        parse_records(output)
+            .into_iter()
+            .find(|r| r.step == Step::Part(part))
+            .and_then(|r| r.answer)
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +581,166 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answer, parse_exec_time, wait_with_timeout};
 
-        use crate::day;
+        use crate::puzzle;
+        use crate::template::record::{Record, Step};
+        use crate::template::timings::{Memory, Stats};
+        use std::process::Command;
+        use std::time::Duration;
+
+        fn record(step: Step, answer: Option<&str>, nanos: u64, samples: usize) -> String {
+            let stats = Stats::from_durations(&vec![Duration::from_nanos(nanos); samples]);
+            Record::new(step, answer.map(str::to_string), stats).to_line()
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    record(Step::Part(1), Some("0"), 74, 100000),
+                    record(Step::Part(2), Some("10"), 74_130_000, 99999),
                     "".into(),
                 ],
-                day!(1),
+                puzzle!(2016, 1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats.unwrap().samples, 100000);
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
+                    record(
+                        Step::Part(1),
+                        Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+                        2_000_000_000,
+                        5,
+                    ),
+                    record(
+                        Step::Part(2),
+                        Some("10s\n(100ms @ 1 samples)"),
+                        100_000_000,
+                        1,
+                    ),
                     "".into(),
                 ],
-                day!(1),
+                puzzle!(2016, 1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
+        }
+
+        #[test]
+        fn parses_parse_step() {
+            let res = parse_exec_time(
+                &[
+                    record(Step::Parse, None, 1_000_000, 10),
+                    record(Step::Part(1), Some("1"), 1_000, 10),
+                ],
+                puzzle!(2016, 1),
+            );
+            assert_approx_eq!(res.total_nanos, 1001000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0ms");
+            assert_eq!(res.parse_stats.unwrap().samples, 10);
+            assert_eq!(res.part_2, None);
         }
 
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
+                    record(Step::Part(1), None, 10, 1),
+                    record(Step::Part(2), None, 10, 1),
                     "".into(),
                 ],
-                day!(1),
+                puzzle!(2016, 1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert!(res.failed.is_empty());
+        }
+
+        #[test]
+        fn parses_failed_parts() {
+            let stats = Stats::from_durations(&[Duration::from_nanos(10)]);
+            let mut failed = Record::new(Step::Part(2), None, stats);
+            failed.error = Some("Part 2 failed: invalid input".into());
+
+            let res = parse_exec_time(
+                &[record(Step::Part(1), Some("1"), 10, 1), failed.to_line()],
+                puzzle!(2016, 1),
+            );
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.failed, vec![2]);
         }
+
+        #[test]
+        fn parses_memory() {
+            let stats = Stats::from_durations(&[Duration::from_nanos(10)]);
+            let mut part_1 = Record::new(Step::Part(1), Some("1".into()), stats);
+            part_1.memory = Some(Memory {
+                allocations: 2,
+                total_bytes: 64,
+                peak_bytes: 32,
+            });
+
+            let res = parse_exec_time(
+                &[part_1.to_line(), record(Step::Part(2), Some("2"), 10, 1)],
+                puzzle!(2016, 1),
+            );
+            assert_eq!(res.part_1_memory.map(|m| m.allocations), Some(2));
+            assert_eq!(res.part_2_memory, None);
+        }
+
+        #[test]
+        fn kills_children_after_timeout() {
+            let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+            let timeout = Some(Duration::from_millis(50));
+            assert!(wait_with_timeout(&mut child, timeout).unwrap());
+
+            let mut child = Command::new("true").spawn().unwrap();
+            assert!(!wait_with_timeout(&mut child, Some(Duration::from_secs(5))).unwrap());
+        }
+
+        #[test]
+        fn parses_answers() {
+            let output = [
+                "debug output".into(),
+                record(Step::Part(1), Some("42"), 10, 1),
+                record(Step::Part(2), None, 10, 1),
+            ];
+            assert_eq!(parse_answer(&output, 1), Some("42".into()));
+            assert_eq!(parse_answer(&output, 2), None);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::run_ordered;
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn emits_results_in_order() {
+        let items = [30, 0, 20, 10, 0];
+        let mut emitted = vec![];
+
+        run_ordered(
+            &items,
+            3,
+            |millis| {
+                thread::sleep(Duration::from_millis(*millis));
+                millis * 2
+            },
+            |item, result| emitted.push((*item, result)),
+        );
+
+        assert_eq!(emitted, vec![(30, 60), (0, 0), (20, 40), (10, 20), (0, 0)]);
     }
 }
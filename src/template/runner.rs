@@ -1,65 +1,380 @@
 /// Encapsulates code that interacts with solution functions.
+use std::ffi::OsString;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::{Answers, Verdict};
+use crate::template::cli;
+use crate::template::input::InputSource;
+use crate::template::record::{Record, Step};
+use crate::template::timings::{Memory, Stats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The value returned by a solution part: `Option<T>`, where `None` means the part is not solved yet,
+/// or `Result<T, E>` for solutions that can fail, e.g. on malformed input.
+pub trait PartResult {
+    fn answer(&self) -> Option<String>;
+
+    /// Describes why the part failed, including the chain of causes for `eyre` reports.
+    fn error(&self) -> Option<String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn answer(&self) -> Option<String> {
+        self.as_ref().map(ToString::to_string)
+    }
+
+    fn error(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn answer(&self) -> Option<String> {
+        self.as_ref().ok().map(ToString::to_string)
+    }
+
+    fn error(&self) -> Option<String> {
+        self.as_ref().err().map(|e| format!("{e:#}"))
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
+    let SolutionArgs { json, bench, .. } = *SolutionArgs::from_env();
+
+    let (result, stats, memory) = run_timed(
+        func,
+        input,
+        |result| {
+            if !json {
+                print_result(&result.answer(), result.error().is_some(), &part_str, "");
+                announce_bench(bench);
+            }
+        },
+        bench,
+    );
+
+    let mut record = part_record(&result, part, stats);
+    record.memory = memory;
+
+    if json {
+        println!("{}", record.to_line());
+    } else {
+        print_record(&record);
+    }
+
+    if let Some(answer) = record.answer {
+        if let Some(Err(e)) = submit_result(answer, puzzle, part) {
+            eprintln!("Failed to submit: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn part_record(result: &impl PartResult, part: u8, stats: Stats) -> Record {
+    let mut record = Record::new(Step::Part(part), result.answer(), stats);
+    record.error = result.error().map(|e| format!("Part {part} failed: {e}"));
+    record
+}
+
+/// Run the optional parse step of a solution, timing it like a part.
+/// The parsed value is then shared by both parts.
+pub fn run_parse<I: Clone, P>(func: impl Fn(I) -> P, input: I) -> P {
+    let SolutionArgs { json, bench, .. } = *SolutionArgs::from_env();
+
+    let (parsed, stats, memory) = run_timed(
+        func,
+        input,
+        |_| {
+            if !json {
+                print!("Parse:");
+                announce_bench(bench);
+            }
+        },
+        bench,
+    );
+
+    let mut record = Record::new(Step::Parse, None, stats);
+    record.memory = memory;
+
+    if json {
+        println!("{}", record.to_line());
+    } else {
+        print_record(&record);
+    }
+
+    parsed
+}
+
+/// Run a solution part without printing anything, returning its record.
+/// Used when running solutions in-process.
+pub fn record_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+    bench: Option<BenchConfig>,
+) -> Record {
+    let (result, stats, memory) = run_timed(func, input, |_| {}, bench);
+    let mut record = part_record(&result, part, stats);
+    record.memory = memory;
+    record
+}
+
+/// Run the parse step of a solution without printing anything, returning its record.
+/// Used when running solutions in-process.
+pub fn record_parse<I: Clone, P>(
+    func: impl Fn(I) -> P,
+    input: I,
+    bench: Option<BenchConfig>,
+) -> (P, Record) {
+    let (parsed, stats, memory) = run_timed(func, input, |_| {}, bench);
+    let mut record = Record::new(Step::Parse, None, stats);
+    record.memory = memory;
+    (parsed, record)
+}
+
+/// Options of a solution binary, see [`cli::SOLUTION`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolutionArgs {
+    /// Print machine-readable records with `--json`.
+    pub json: bool,
+    /// Bench each part with `--time`.
+    pub bench: Option<BenchConfig>,
+    /// Part to submit with `--submit <part>`.
+    pub submit: Option<u8>,
+    /// Only report the heap usage with `--memory`, without writing a dhat profile.
+    pub memory: bool,
+    pub input: InputSource,
+}
+
+impl SolutionArgs {
+    pub fn parse(mut args: Vec<String>) -> Result<Self, String> {
+        cli::SOLUTION.check_flags(&args)?;
+        let input = InputSource::take_from_args(&mut args)?;
+
+        let mut args =
+            pico_args::Arguments::from_vec(args.into_iter().map(OsString::from).collect());
+        let time = args.contains("--time");
+        let bench = BenchConfig::from_args(&mut args).map_err(|e| e.to_string())?;
+        let submit: Option<u8> = args
+            .opt_value_from_str("--submit")
+            .map_err(|e| e.to_string())?;
+
+        if submit.is_some_and(|part| part != 1 && part != 2) {
+            return Err("`--submit` expects part 1 or 2.".into());
+        }
+
+        let parsed = Self {
+            json: args.contains("--json"),
+            bench: time.then_some(bench),
+            submit,
+            memory: args.contains("--memory"),
+            input,
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(format!("unexpected argument(s): {remaining:?}."));
+        }
+
+        Ok(parsed)
+    }
+
+    /// The options of the current process, parsed once. Exits with the reason if they are invalid.
+    pub fn from_env() -> &'static Self {
+        static ARGS: OnceLock<SolutionArgs> = OnceLock::new();
+
+        ARGS.get_or_init(|| {
+            let args: Vec<String> = env::args().skip(1).collect();
+
+            if cli::wants_help(&args) {
+                print!("{}", cli::SOLUTION.help());
+                process::exit(0);
+            }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+            Self::parse(args).unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            })
+        })
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    /// Arguments that pass these options to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.json {
+            args.push("--json".into());
+        }
+        if let Some(bench) = self.bench {
+            args.push("--time".into());
+            args.extend(bench.to_args());
+        }
+        if let Some(part) = self.submit {
+            args.push("--submit".into());
+            args.push(part.to_string());
+        }
+        if self.memory {
+            args.push("--memory".into());
+        }
+        args.extend(self.input.to_args());
+        args
+    }
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+fn announce_bench(bench: Option<BenchConfig>) {
+    if bench.is_some() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Print a record in the human-readable format used by the runner.
+pub fn print_record(record: &Record) {
+    let mut duration_str = format_duration(&record.stats);
+    if let Some(memory) = record.memory {
+        duration_str.push_str(&format!(" [heap: {memory}]"));
+    }
+
+    match record.step {
+        Step::Parse => {
+            print!("\r");
+            println!("Parse:{duration_str}");
+        }
+        Step::Part(part) => print_result(
+            &record.answer,
+            record.error.is_some(),
+            &format!("Part {part}"),
+            &duration_str,
+        ),
+    }
+
+    if let Some(error) = &record.error {
+        eprintln!("{error}");
+    }
+}
+
+/// Options controlling how solution parts are benched.
+/// Passed to solution binaries as `--bench-budget <ms>` and `--bench-iterations <n>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate execution time to spend benching a part. Defaults to 1 second.
+    pub budget: Option<Duration>,
+    /// Exact number of measured iterations, takes precedence over the time budget.
+    pub iterations: Option<u128>,
+}
+
+impl BenchConfig {
+    /// Read the bench options of a command.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            budget: args
+                .opt_value_from_str("--bench-budget")?
+                .map(Duration::from_millis),
+            iterations: args.opt_value_from_str("--bench-iterations")?,
+        })
+    }
+
+    /// Arguments that mirror this config to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(budget) = self.budget {
+            args.push("--bench-budget".into());
+            args.push(budget.as_millis().to_string());
+        }
+        if let Some(iterations) = self.iterations {
+            args.push("--bench-iterations".into());
+            args.push(iterations.to_string());
+        }
+        args
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether we are benching:
+///  1. by default, the function is executed once.
+///  2. with `--time`, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Builds with the `dhat-heap` or `count-allocs` feature also measure the heap usage of the first run.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    bench_config: Option<BenchConfig>,
+) -> (T, Stats, Option<Memory>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
+        // with `--memory`, only the heap stats are needed, skip writing and printing the profile.
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = if SolutionArgs::from_env().memory {
+            dhat::Profiler::builder().testing().build()
+        } else {
+            dhat::Profiler::new_heap()
+        };
+
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        let (result, memory) = {
+            let (result, memory) = crate::template::alloc::measure(|| func(input));
+            (result, Some(memory))
+        };
+        #[cfg(not(all(feature = "count-allocs", not(feature = "dhat-heap"))))]
+        let result = func(input);
 
-        func(input)
+        #[cfg(feature = "dhat-heap")]
+        let memory = {
+            let stats = dhat::HeapStats::get();
+            Some(Memory {
+                allocations: stats.total_blocks,
+                total_bytes: stats.total_bytes,
+                peak_bytes: stats.max_bytes as u64,
+            })
+        };
+        #[cfg(not(any(feature = "dhat-heap", feature = "count-allocs")))]
+        let memory = None;
+
+        (result, memory)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let stats = match bench_config {
+        Some(config) => bench(func, input, &base_time, config),
+        None => Stats::from_durations(&[base_time]),
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: BenchConfig,
+) -> Stats {
+    let budget = config.budget.unwrap_or(Duration::from_secs(1));
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let bench_iterations = config.iterations.unwrap_or_else(|| {
+        (budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000)
+    });
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // discard the first runs, which are skewed by cold caches and lazy allocations.
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,35 +386,44 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_durations(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+fn format_duration(stats: &Stats) -> String {
+    let median = stats.median();
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+    if stats.samples == 1 {
+        format!(" ({median:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({median:.1?} @ {} samples) [mean {:.1?}, min {:.1?}, max {:.1?}, stddev {:.1?}, p95 {:.1?}]",
+            stats.samples,
+            stats.mean(),
+            stats.min(),
+            stats.max(),
+            stats.std_dev(),
+            stats.p95()
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Prints `✖` for parts that are not solved yet and `⚠` for parts that failed.
+fn print_result(result: &Option<String>, failed: bool, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    if failed {
+        if is_intermediate_result {
+            print!("{part}: ⚠");
+        } else {
+            print!("\r");
+            println!("{part}: ⚠ failed{duration_str}");
+        }
+        return;
+    }
+
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -131,39 +455,125 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
+///  2. a session cookie is configured or aoc-cli is installed.
+///  3. the answer was computed from the puzzle input.
+///  4. the answer ledger does not rule the answer out.
+fn submit_result(
+    answer: String,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+) -> Option<Result<String, aoc_client::AocClientError>> {
+    let args = SolutionArgs::from_env();
 
-    if !args.contains(&"--submit".into()) {
+    if args.submit != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if !args.input.is_puzzle() {
+        eprintln!("Not submitting `{answer}`: the answer was computed from a custom input.");
+        return None;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let mut answers = match Answers::load() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not submitting `{answer}`: failed to read answer ledger {e}");
+            process::exit(1);
+        }
     };
 
-    if part_submit != part {
+    if let Err(rejection) = answers.check(puzzle, part, &answer) {
+        eprintln!("Not submitting `{answer}`: {rejection}");
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let backend = match aoc_client::backend() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("Alternatively, run \"cargo install aoc-cli\" to use aoc-cli.");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let response = backend.submit(puzzle, part, &answer);
+
+    if let Some(verdict) = response
+        .as_ref()
+        .ok()
+        .and_then(|m| Verdict::from_message(m))
+    {
+        answers.record(puzzle, part, &answer, verdict);
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store answer ledger: {e}");
+        }
+    }
+
+    Some(response)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchConfig, SolutionArgs};
+    use crate::template::input::InputSource;
+    use std::time::Duration;
+
+    fn parse(args: &[&str]) -> Result<SolutionArgs, String> {
+        SolutionArgs::parse(args.iter().map(|x| x.to_string()).collect())
+    }
+
+    #[test]
+    fn parses_solution_args() {
+        let args = parse(&[
+            "--json",
+            "--time",
+            "--bench-budget",
+            "500",
+            "--example",
+            "nested",
+        ]);
+        assert_eq!(
+            args,
+            Ok(SolutionArgs {
+                json: true,
+                bench: Some(BenchConfig {
+                    budget: Some(Duration::from_millis(500)),
+                    iterations: None,
+                }),
+                submit: None,
+                memory: false,
+                input: InputSource::Example(Some("nested".into())),
+            })
+        );
+
+        assert_eq!(parse(&["--submit", "2"]).unwrap().submit, Some(2));
+        assert_eq!(parse(&[]), Ok(SolutionArgs::default()));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn rejects_invalid_solution_args() {
+        assert!(parse(&["--submit", "3"]).is_err());
+        assert!(parse(&["--submit"]).is_err());
+        assert!(parse(&["--time", "--bench-budget", "fast"]).is_err());
+        assert!(parse(&["--jsn"]).is_err());
+        assert!(parse(&["extra"]).is_err());
+    }
+
+    #[test]
+    fn mirrors_solution_args() {
+        let args = SolutionArgs {
+            json: true,
+            bench: Some(BenchConfig {
+                budget: None,
+                iterations: Some(10),
+            }),
+            submit: Some(1),
+            memory: true,
+            input: InputSource::File("-".into()),
+        };
+        assert_eq!(SolutionArgs::parse(args.to_args()), Ok(args));
+    }
 }
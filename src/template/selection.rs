@@ -0,0 +1,220 @@
+/// Selects the days of commands that run several days, e.g. `cargo time 1-7` or `cargo all 3,5,12 --unsolved`.
+/// A list of days and ranges can be combined with the `--unsolved` and `--slow` filters.
+use std::collections::{BTreeSet, HashSet};
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day, PuzzleId, Year};
+
+/// Days whose last stored run took longer than this are selected by `--slow`.
+const SLOW_THRESHOLD_NANOS: f64 = 1e9;
+
+/// A list of days like `1-7,12`, parsed from the free argument of a command.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DayList(pub BTreeSet<Day>);
+
+impl FromStr for DayList {
+    type Err = DayListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |x: &str| {
+            x.trim()
+                .parse::<Day>()
+                .map_err(|_| DayListError::InvalidDay(x.trim().to_string()))
+        };
+
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',') {
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_day(start)?, parse_day(end)?);
+                    if start > end {
+                        return Err(DayListError::InvalidRange(item.to_string()));
+                    }
+                    days.extend(all_days().filter(|d| (start..=end).contains(d)));
+                }
+                None => {
+                    days.insert(parse_day(item)?);
+                }
+            }
+        }
+
+        Ok(DayList(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DayList`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum DayListError {
+    InvalidDay(String),
+    InvalidRange(String),
+}
+
+impl Error for DayListError {}
+
+impl Display for DayListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayListError::InvalidDay(day) => {
+                write!(
+                    f,
+                    "invalid day `{day}`, expecting a number between 1 and 25"
+                )
+            }
+            DayListError::InvalidRange(range) => {
+                write!(
+                    f,
+                    "invalid range `{range}`, the first day has to come first"
+                )
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The days a command runs: the listed days, or all days, narrowed down by the filters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DaySelection {
+    pub days: Option<DayList>,
+    /// Only scaffolded days without an accepted answer for both parts.
+    pub unsolved: bool,
+    /// Only days whose last stored run took longer than a second.
+    pub slow: bool,
+}
+
+impl DaySelection {
+    /// Whether no days or filters were passed. Commands pick their own default in this case.
+    pub fn is_empty(&self) -> bool {
+        self.days.is_none() && !self.unsolved && !self.slow
+    }
+
+    /// Resolve the selected days of a year, reading the answers and stored timings if needed.
+    pub fn resolve(&self, year: Year) -> HashSet<Day> {
+        let answers = if self.unsolved {
+            Answers::read_from_file()
+        } else {
+            Answers::default()
+        };
+        let timings = if self.slow {
+            Timings::read_from_file()
+        } else {
+            Timings::default()
+        };
+
+        self.resolve_with(year, &answers, &timings, |puzzle| {
+            Path::new(&get_path_for_bin(puzzle)).exists()
+        })
+    }
+
+    fn resolve_with(
+        &self,
+        year: Year,
+        answers: &Answers,
+        timings: &Timings,
+        is_scaffolded: impl Fn(PuzzleId) -> bool,
+    ) -> HashSet<Day> {
+        let is_unsolved = |puzzle: PuzzleId| {
+            is_scaffolded(puzzle)
+                && (1..=2).any(|part| answers.correct_answer(puzzle, part).is_none())
+        };
+        let is_slow = |puzzle: PuzzleId| {
+            timings
+                .data
+                .iter()
+                .any(|t| t.puzzle == puzzle && t.total_nanos > SLOW_THRESHOLD_NANOS)
+        };
+
+        all_days()
+            .filter(|day| match &self.days {
+                Some(list) => list.0.contains(day),
+                None => true,
+            })
+            .filter(|day| !self.unsolved || is_unsolved(PuzzleId::new(year, *day)))
+            .filter(|day| !self.slow || is_slow(PuzzleId::new(year, *day)))
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{DayList, DayListError, DaySelection};
+    use crate::template::answers::{Answers, Verdict};
+    use crate::template::timings::Timings;
+    use crate::{day, puzzle, year};
+
+    fn parse(s: &str) -> Result<Vec<u8>, DayListError> {
+        s.parse::<DayList>()
+            .map(|list| list.0.into_iter().map(|d| d.into_inner()).collect())
+    }
+
+    #[test]
+    fn parses_day_lists() {
+        assert_eq!(parse("4"), Ok(vec![4]));
+        assert_eq!(parse("1-3"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse("12,3,5"), Ok(vec![3, 5, 12]));
+        assert_eq!(parse("1-3,2,20-21"), Ok(vec![1, 2, 3, 20, 21]));
+    }
+
+    #[test]
+    fn rejects_invalid_day_lists() {
+        assert_eq!(parse("0"), Err(DayListError::InvalidDay("0".into())));
+        assert_eq!(parse("1-26"), Err(DayListError::InvalidDay("26".into())));
+        assert_eq!(parse("3,"), Err(DayListError::InvalidDay(String::new())));
+        assert_eq!(parse("7-1"), Err(DayListError::InvalidRange("7-1".into())));
+    }
+
+    #[test]
+    fn filters_selection() {
+        let mut answers = Answers::default();
+        answers.record(puzzle!(2016, 1), 1, "1", Verdict::Correct);
+        answers.record(puzzle!(2016, 1), 2, "2", Verdict::Correct);
+        answers.record(puzzle!(2016, 2), 1, "3", Verdict::Correct);
+
+        let timings = Timings::try_from(
+            r#"{ "data": [{ "year": "2016", "day": "03", "part_1": "2s", "part_2": null, "total_nanos": 2000000000 }] }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        let scaffolded = |puzzle: crate::template::PuzzleId| puzzle.day <= 3;
+        let resolve = |selection: DaySelection| {
+            selection.resolve_with(year!(2016), &answers, &timings, scaffolded)
+        };
+
+        let unsolved = DaySelection {
+            unsolved: true,
+            ..DaySelection::default()
+        };
+        assert_eq!(resolve(unsolved), HashSet::from([day!(2), day!(3)]));
+
+        let slow = DaySelection {
+            slow: true,
+            ..DaySelection::default()
+        };
+        assert_eq!(resolve(slow), HashSet::from([day!(3)]));
+
+        let listed = DaySelection {
+            days: Some("1-2".parse().unwrap()),
+            unsolved: true,
+            ..DaySelection::default()
+        };
+        assert_eq!(resolve(listed), HashSet::from([day!(2)]));
+
+        let single = DaySelection {
+            days: Some("7".parse().unwrap()),
+            ..DaySelection::default()
+        };
+        assert_eq!(resolve(single), HashSet::from([day!(7)]));
+    }
+}
@@ -1,24 +1,226 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{Day, PuzzleId, Year};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Summary statistics of the samples taken while benching a solution part.
+/// All durations are stored in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub std_dev_nanos: f64,
+    pub p95_nanos: f64,
+}
+
+impl Stats {
+    /// Compute statistics for a non-empty set of samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_durations(durations: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = durations.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        let median = if n % 2 == 0 {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0
+        } else {
+            nanos[n / 2]
+        };
+
+        // nearest-rank percentile.
+        let p95_index = (n * 95).div_ceil(100).saturating_sub(1);
+
+        Self {
+            samples: n as u128,
+            mean_nanos: mean,
+            median_nanos: median,
+            min_nanos: nanos[0],
+            max_nanos: nanos[n - 1],
+            std_dev_nanos: variance.sqrt(),
+            p95_nanos: nanos[p95_index],
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        Duration::from_secs_f64(self.mean_nanos / 1e9)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_secs_f64(self.median_nanos / 1e9)
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_secs_f64(self.min_nanos / 1e9)
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_secs_f64(self.max_nanos / 1e9)
+    }
+
+    pub fn std_dev(&self) -> Duration {
+        Duration::from_secs_f64(self.std_dev_nanos / 1e9)
+    }
+
+    pub fn p95(&self) -> Duration {
+        Duration::from_secs_f64(self.p95_nanos / 1e9)
+    }
+}
+
+/// Heap usage of a solution part, measured with dhat or the counting allocator of `count-allocs`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    /// Number of allocations.
+    pub allocations: u64,
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Bytes allocated at the peak of heap usage.
+    pub peak_bytes: u64,
+}
+
+impl Memory {
+    /// Combine the memory of the steps of a day: allocations add up, the peak is the largest peak.
+    pub fn combine(memory: impl IntoIterator<Item = Memory>) -> Option<Memory> {
+        memory.into_iter().reduce(|a, b| Memory {
+            allocations: a.allocations + b.allocations,
+            total_bytes: a.total_bytes + b.total_bytes,
+            peak_bytes: a.peak_bytes.max(b.peak_bytes),
+        })
+    }
+}
+
+impl Display for Memory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} total, {} peak",
+            self.allocations,
+            format_bytes(self.total_bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
+    pub puzzle: PuzzleId,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
     pub total_nanos: f64,
+    /// Unix timestamp (in seconds) of the run, set when stored.
+    pub recorded_at: Option<u64>,
+    /// Short hash of the git commit that was benched, if available.
+    pub commit: Option<String>,
+    /// Parts that returned an error instead of an answer. Like unsolved parts, they are not timed.
+    pub failed: Vec<u8>,
+    /// Whether the run was killed by `--timeout`. Parts that did not finish in time are not timed.
+    pub timed_out: bool,
+    /// Heap usage of each step, only measured with `cargo time --memory`.
+    pub parse_memory: Option<Memory>,
+    pub part_1_memory: Option<Memory>,
+    pub part_2_memory: Option<Memory>,
+}
+
+impl Timing {
+    /// A timing of a day that was not measured yet.
+    pub fn new(puzzle: PuzzleId) -> Self {
+        Self {
+            puzzle,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            total_nanos: 0_f64,
+            recorded_at: None,
+            commit: None,
+            failed: vec![],
+            timed_out: false,
+            parse_memory: None,
+            part_1_memory: None,
+            part_2_memory: None,
+        }
+    }
+
+    /// Heap usage of the whole day, if it was measured.
+    pub fn memory(&self) -> Option<Memory> {
+        Memory::combine(
+            [self.parse_memory, self.part_1_memory, self.part_2_memory]
+                .into_iter()
+                .flatten(),
+        )
+    }
 }
 
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// The latest timing of each day.
     pub data: Vec<Timing>,
+    /// Timings that were superseded by a later run, oldest first.
+    pub history: Vec<Timing>,
+}
+
+/// Change of a day's total median time compared to its last stored run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub puzzle: PuzzleId,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    pub baseline_commit: Option<String>,
+}
+
+impl Comparison {
+    /// Relative change, e.g. `0.25` if the day got 25% slower.
+    pub fn change(&self) -> f64 {
+        self.current_nanos / self.baseline_nanos - 1.0
+    }
+
+    /// Whether the day got slower by more than `threshold`, e.g. `0.1` for 10%.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
 }
 
 impl Timings {
@@ -45,21 +247,66 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Overwritten timings are kept in the history.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
+        let mut history: Vec<Timing> = self.history.clone();
+        history.extend(new.history.iter().cloned());
 
         for timing in &new.data {
             data.push(timing.clone());
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if data.iter().any(|t| t.puzzle == timing.puzzle) {
+                history.push(timing.clone());
+            } else {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|t| t.puzzle);
+        Timings { data, history }
+    }
+
+    /// Mark all timings with the current time and git commit.
+    pub fn stamp(&mut self) {
+        let recorded_at = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs());
+        let commit = current_commit();
+
+        for timing in &mut self.data {
+            timing.recorded_at = recorded_at;
+            timing.commit.clone_from(&commit);
+        }
+    }
+
+    /// Compare timings with the latest timings of `baseline`.
+    /// Days are skipped if they are missing from the baseline or did not solve the same parts.
+    pub fn compare(&self, baseline: &Timings) -> Vec<Comparison> {
+        self.data
+            .iter()
+            .filter_map(|timing| {
+                let previous = baseline.data.iter().find(|t| t.puzzle == timing.puzzle)?;
+
+                let same_parts = previous.part_1.is_some() == timing.part_1.is_some()
+                    && previous.part_2.is_some() == timing.part_2.is_some();
+
+                (same_parts && previous.total_nanos > 0.0).then(|| Comparison {
+                    puzzle: timing.puzzle,
+                    baseline_nanos: previous.total_nanos,
+                    current_nanos: timing.total_nanos,
+                    baseline_commit: previous.commit.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// The run of a day that preceded its latest timing, if any.
+    pub fn previous(&self, puzzle: PuzzleId) -> Option<&Timing> {
+        self.history.iter().rev().find(|t| t.puzzle == puzzle)
     }
 
     /// Sum up total duration of timings as millis.
@@ -67,13 +314,25 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.puzzle == puzzle && t.part_1.is_some() && t.part_2.is_some())
     }
 }
 
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -85,6 +344,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -95,19 +359,34 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // timings stored before history was kept do not have the key.
+        let no_history = vec![];
+        let json_history = match json.get("history") {
+            None => &no_history,
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?,
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(Timing::try_from)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -118,9 +397,48 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year.to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        #[allow(clippy::cast_precision_loss)]
+        let recorded_at = value.recorded_at.map(|t| JsonValue::Number(t as f64));
+        map.insert("recorded_at".into(), recorded_at.unwrap_or(JsonValue::Null));
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "failed".into(),
+            JsonValue::Array(
+                value
+                    .failed
+                    .iter()
+                    .map(|part| JsonValue::Number(f64::from(*part)))
+                    .collect(),
+            ),
+        );
+
+        map.insert("timed_out".into(), JsonValue::Boolean(value.timed_out));
+
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -140,10 +458,145 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, memory) in [
+            ("parse_memory", &value.parse_memory),
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            map.insert(
+                key.into(),
+                memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert(
+            "std_dev_nanos".into(),
+            JsonValue::Number(value.std_dev_nanos),
+        );
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+
         JsonValue::Object(map)
     }
 }
 
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            samples: number("samples")? as u128,
+            mean_nanos: number("mean_nanos")?,
+            median_nanos: number("median_nanos")?,
+            min_nanos: number("min_nanos")?,
+            max_nanos: number("max_nanos")?,
+            std_dev_nanos: number("std_dev_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Memory> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Memory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Memory {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(Memory {
+            allocations: number("allocations")?,
+            total_bytes: number("total_bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/// Parse an optional memory object. Only present for timings that were stored with `--memory`.
+pub fn parse_optional_memory(value: Option<&JsonValue>) -> Result<Option<Memory>, String> {
+    match value {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(v) => Memory::try_from(v).map(Some),
+    }
+}
+
+/// Parse an optional stats object. Timings stored before stats were recorded do not have the key.
+fn parse_optional_stats(value: Option<&JsonValue>) -> Result<Option<Stats>, String> {
+    match value {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(v) => Stats::try_from(v).map(Some),
+    }
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -152,12 +605,27 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected timing.year to be a Year struct.")?;
+
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // timings stored before parse steps were timed do not have the key.
+        let parse = match json.get("parse") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+        };
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -173,11 +641,72 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored before history was kept do not have these keys.
+        let recorded_at = match json.get("recorded_at") {
+            None | Some(JsonValue::Null) => None,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|t| *t as u64)
+                    .ok_or("Expected timing.recorded_at to be null or number.")?,
+            ),
+        };
+
+        let commit = match json.get("commit") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.commit to be null or string.")?,
+            ),
+        };
+
+        // timings stored before failures were recorded do not have the key.
+        let failed = match json.get("failed") {
+            None | Some(JsonValue::Null) => vec![],
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|parts| {
+                    parts
+                        .iter()
+                        .map(|p| p.get::<f64>().map(|p| *p as u8))
+                        .collect::<Option<Vec<u8>>>()
+                })
+                .ok_or("Expected timing.failed to be an array of numbers.")?,
+        };
+
+        let timed_out = match json.get("timed_out") {
+            None | Some(JsonValue::Null) => false,
+            Some(v) => *v
+                .get::<bool>()
+                .ok_or("Expected timing.timed_out to be a boolean.")?,
+        };
+
+        let parse_memory = parse_optional_memory(json.get("parse_memory"))?;
+        let part_1_memory = parse_optional_memory(json.get("part_1_memory"))?;
+        let part_2_memory = parse_optional_memory(json.get("part_2_memory"))?;
+
+        let parse_stats = parse_optional_stats(json.get("parse_stats"))?;
+        let part_1_stats = parse_optional_stats(json.get("part_1_stats"))?;
+        let part_2_stats = parse_optional_stats(json.get("part_2_stats"))?;
+
         Ok(Timing {
-            day,
+            puzzle: PuzzleId::new(year, day),
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
             total_nanos,
+            recorded_at,
+            commit,
+            failed,
+            timed_out,
+            parse_memory,
+            part_1_memory,
+            part_2_memory,
         })
     }
 }
@@ -186,7 +715,7 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::puzzle;
 
     use super::{Timing, Timings};
 
@@ -194,42 +723,113 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(puzzle!(2016, 1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(puzzle!(2016, 2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::new(puzzle!(2016, 4))
                 },
             ],
+            history: vec![],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            puzzle,
+            template::timings::{format_bytes, Memory, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.puzzle, puzzle!(2016, 1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "part_1_stats": { "samples": 10, "mean_nanos": 2, "median_nanos": 1, "min_nanos": 0.5, "max_nanos": 4, "std_dev_nanos": 0.1, "p95_nanos": 3 }, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median_nanos, 1_f64);
+            assert_eq!(stats.p95_nanos, 3_f64);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_history() {
+            let json = r#"{ "data": [], "history": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "recorded_at": 1700000000, "commit": "abc1234" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 0);
+            let timing = timings.history.first().unwrap();
+            assert_eq!(timing.recorded_at, Some(1_700_000_000));
+            assert_eq!(timing.commit.as_deref(), Some("abc1234"));
+        }
+
+        #[test]
+        fn handles_json_failures() {
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "failed": [2], "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].failed, vec![2]);
+
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "failed": ["2"], "total_nanos": 1000000 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn handles_json_timeouts() {
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "timed_out": true, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert!(timings.data[0].timed_out);
+
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            assert!(!Timings::try_from(json).unwrap().data[0].timed_out);
+        }
+
+        #[test]
+        fn handles_json_memory() {
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": "2ms", "part_1_memory": { "allocations": 3, "total_bytes": 2048, "peak_bytes": 1024 }, "part_2_memory": { "allocations": 2, "total_bytes": 512, "peak_bytes": 1536 }, "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = &timings.data[0];
+            assert_eq!(timing.parse_memory, None);
+            assert_eq!(
+                timing.memory(),
+                Some(Memory {
+                    allocations: 5,
+                    total_bytes: 2560,
+                    peak_bytes: 1536
+                })
+            );
+
+            let json = r#"{ "data": [{ "year": "2016", "day": "01", "part_1": "1ms", "part_2": null, "part_1_memory": { "allocations": "3" }, "total_nanos": 1000000 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn formats_bytes() {
+            assert_eq!(format_bytes(512), "512 B");
+            assert_eq!(format_bytes(1536), "1.5 KiB");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,11 +847,38 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json = r#"{ "data": [{ "year": "2016", "day": "01" }, { "year": "2016", "day": "26" }, { "year": "2016", "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
 
+    mod stats {
+        use crate::template::timings::Stats;
+        use std::time::Duration;
+
+        #[test]
+        fn computes_stats() {
+            let durations: Vec<Duration> = (1..=20).map(Duration::from_nanos).collect();
+            let stats = Stats::from_durations(&durations);
+            assert_eq!(stats.samples, 20);
+            assert_eq!(stats.mean_nanos, 10.5);
+            assert_eq!(stats.median_nanos, 10.5);
+            assert_eq!(stats.min_nanos, 1.0);
+            assert_eq!(stats.max_nanos, 20.0);
+            assert_eq!(stats.p95_nanos, 19.0);
+            assert!((stats.std_dev_nanos - 5.766_281).abs() < 1e-6);
+        }
+
+        #[test]
+        fn handles_single_sample() {
+            let stats = Stats::from_durations(&[Duration::from_millis(2)]);
+            assert_eq!(stats.samples, 1);
+            assert_eq!(stats.median(), Duration::from_millis(2));
+            assert_eq!(stats.p95(), Duration::from_millis(2));
+            assert_eq!(stats.std_dev_nanos, 0.0);
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
         use std::collections::HashMap;
@@ -277,7 +904,7 @@ mod tests {
 
     mod is_day_complete {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -285,48 +912,71 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(puzzle!(2016, 1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(puzzle!(2016, 1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(puzzle!(2016, 1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2016, 1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    ..Timing::new(puzzle!(2016, 1))
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2016, 1)), false);
+        }
+    }
+
+    mod compare {
+        use crate::puzzle;
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn flags_slower_days() {
+            let baseline = get_mock_timings();
+            let mut current = get_mock_timings();
+            current.data[0].total_nanos = 3.6e+10;
+            current.data[1].total_nanos = 7.5e+10;
+            current.data[2].part_2 = Some("1ms".into());
+
+            let comparisons = current.compare(&baseline);
+
+            // day 4 solved another part, its times are not comparable.
+            assert_eq!(comparisons.len(), 2);
+            assert_eq!(comparisons[0].puzzle, puzzle!(2016, 1));
+            assert!((comparisons[0].change() - 0.2).abs() < 1e-9);
+            assert!(comparisons[0].is_regression(0.1));
+            assert!(!comparisons[1].is_regression(0.1));
         }
     }
 
     mod merge {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -337,18 +987,16 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Timing::new(puzzle!(2016, 3))
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2016, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2016, 2));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2016, 3));
+            assert_eq!(merged.data[3].puzzle, puzzle!(2016, 4));
         }
 
         #[test]
@@ -357,19 +1005,21 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Timing::new(puzzle!(2016, 2))
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2016, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2016, 2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2016, 4));
+
+            assert_eq!(merged.history.len(), 1);
+            assert_eq!(merged.history[0].puzzle, puzzle!(2016, 2));
+            assert_eq!(merged.history[0].total_nanos, 7e+10);
         }
 
         #[test]